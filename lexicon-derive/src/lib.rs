@@ -0,0 +1,94 @@
+//! `#[derive(Symbolic)]` for the `lexicon` crate, re-exported by it with the
+//! `derive` feature enabled.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Field, Fields, Index, Member, Type};
+
+/// Implements `Symbolic` for a struct by returning the symbol of its field
+/// annotated with `#[sym]`, or else of its only field of type `Symbol`. The
+/// annotated field may be of any type implementing `Symbolic` itself.
+#[proc_macro_derive(Symbolic, attributes(sym))]
+pub fn derive_symbolic(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            let msg = "`Symbolic` can only be derived for structs";
+            return Err(Error::new(input.ident.span(), msg));
+        }
+    };
+    let member = symbol_field(fields, input)?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::lexicon::Symbolic for #name #ty_generics #where_clause {
+            fn get_symbol(&self) -> ::lexicon::Symbol {
+                ::lexicon::Symbolic::get_symbol(&self.#member)
+            }
+        }
+    })
+}
+
+/// Finds the field whose symbol is to be returned.
+fn symbol_field(fields: &Fields, input: &DeriveInput) -> syn::Result<Member> {
+    let member = |(idx, field): (usize, &Field)| match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index {
+            index: idx as u32,
+            span: field.span(),
+        }),
+    };
+
+    let annotated = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| field.attrs.iter().any(|a| a.path().is_ident("sym")))
+        .collect::<Vec<_>>();
+    match annotated.as_slice() {
+        [field] => return Ok(member(*field)),
+        [_, (_, field), ..] => {
+            let msg = "only a single field may be annotated with `#[sym]`";
+            return Err(Error::new(field.span(), msg));
+        }
+        [] => {}
+    }
+
+    let symbols = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| is_symbol(&field.ty))
+        .collect::<Vec<_>>();
+    match symbols.as_slice() {
+        [field] => Ok(member(*field)),
+        [] => {
+            let msg = "no field of type `Symbol`; annotate the field to use with `#[sym]`";
+            Err(Error::new(input.ident.span(), msg))
+        }
+        [_, (_, field), ..] => {
+            let msg = "multiple fields of type `Symbol`; annotate the one to use with `#[sym]`";
+            Err(Error::new(field.span(), msg))
+        }
+    }
+}
+
+/// Whether `ty` names `Symbol` (through any path, e.g., `lexicon::Symbol`).
+fn is_symbol(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Symbol" && segment.arguments.is_empty()),
+        _ => false,
+    }
+}
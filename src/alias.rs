@@ -0,0 +1,130 @@
+use core::hash::BuildHasher;
+
+use crate::{Backend, Key, Lexicon, LexiconError};
+
+impl<K: Key, H: BuildHasher, B: Backend> Lexicon<K, H, B> {
+    /// Registers `spelling` as an alternative spelling of `id` (e.g., a
+    /// deprecated name), such that interning (or getting) `spelling` returns
+    /// `id`. `id` still resolves to the string it was interned with.
+    ///
+    /// # Panics
+    /// Panics if `id` was not interned in this `Lexicon`, or if `spelling` is
+    /// already interned as another key. See `try_alias` for a non-panicking
+    /// alternative.
+    pub fn alias(&mut self, id: K, spelling: &str) {
+        if let Err(e) = self.try_alias(id, spelling) {
+            panic!("{} while aliasing `{}`", e, spelling)
+        }
+    }
+
+    /// Like `alias`, but returns an error (leaving this `Lexicon` untouched)
+    /// rather than panicking if `id` was not interned
+    /// (`LexiconError::InvalidKey`), or if `spelling` is already interned as
+    /// another key (`LexiconError::AlreadyInterned`).
+    pub fn try_alias(&mut self, id: K, spelling: &str) -> Result<(), LexiconError> {
+        if self.try_lookup(id).is_none() {
+            return Err(LexiconError::InvalidKey(id.to_usize()));
+        }
+        let hash = self.hasher.hash_one(spelling);
+        match self.find(hash, spelling) {
+            Some(other) if other == id => return Ok(()),
+            Some(other) => return Err(LexiconError::AlreadyInterned(other.to_usize())),
+            None => {}
+        }
+        if spelling.len() > B::MAX_LEN {
+            return Err(LexiconError::StringTooLong);
+        }
+        self.charge(spelling.len())?;
+
+        self.aliases.push((self.backend.alloc(spelling), id));
+        let (aliases, backend, hasher) = (&self.aliases, &self.backend, &self.hasher);
+        self.alias_map
+            .insert_unique(hash, aliases.len() - 1, |&idx| {
+                hasher.hash_one(backend.get(aliases[idx].0))
+            });
+        Ok(())
+    }
+
+    /// Returns every spelling of `id`: the string it was interned with,
+    /// followed by its aliases (see `alias`) in the order they were added.
+    pub fn spellings_of(&self, id: K) -> impl Iterator<Item = &str> + '_ {
+        let aliases = self.aliases.iter().filter(move |&&(_, of)| of == id);
+        let aliases = aliases.map(move |&(span, _)| self.backend.get(span));
+        self.try_lookup(id).into_iter().chain(aliases)
+    }
+
+    /// Returns the key `string` is an alias of, if any.
+    pub(crate) fn find_alias(&self, hash: u64, string: &str) -> Option<K> {
+        if self.aliases.is_empty() {
+            return None;
+        }
+        let (aliases, backend) = (&self.aliases, &self.backend);
+        let idx = self
+            .alias_map
+            .find(hash, |&idx| backend.get(aliases[idx].0) == string)?;
+        Some(aliases[*idx].1)
+    }
+
+    /// Drops the aliases of keys from `len` onwards, as well as those added
+    /// after `mark` was taken.
+    pub(crate) fn truncate_aliases(&mut self, len: usize, mark: B::Mark) {
+        let before = self.aliases.len();
+        let (backend, stored) = (&self.backend, &mut self.stored);
+        self.aliases.retain(|&(span, id)| {
+            let keep = id.to_usize() < len && backend.precedes(span, mark);
+            if !keep {
+                *stored -= backend.get(span).len();
+            }
+            keep
+        });
+        if self.aliases.len() == before {
+            return;
+        }
+        self.alias_map.clear();
+        let (aliases, hasher) = (&self.aliases, &self.hasher);
+        for (idx, &(span, _)) in aliases.iter().enumerate() {
+            let hash = hasher.hash_one(backend.get(span));
+            self.alias_map.insert_unique(hash, idx, |&idx| {
+                hasher.hash_one(backend.get(aliases[idx].0))
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_alias() {
+        let mut lexicon = Lexicon::default();
+        let truth = lexicon.intern("true");
+        lexicon.alias(truth, "TRUE");
+        lexicon.alias(truth, "True");
+        lexicon.alias(truth, "true");
+        assert_eq!(lexicon.intern("TRUE"), truth);
+        assert_eq!(lexicon.get("True"), Some(truth));
+        assert_eq!(lexicon.lookup(truth), "true");
+        let spellings = lexicon.spellings_of(truth).collect::<Vec<_>>();
+        assert_eq!(spellings, ["true", "TRUE", "True"]);
+        assert_eq!(lexicon.len(), 1);
+        lexicon.validate();
+    }
+
+    #[test]
+    fn test_alias_errors() {
+        let mut lexicon = Lexicon::default();
+        let [yes, no] = ["yes", "no"].map(|s| lexicon.intern(s));
+        assert_eq!(
+            lexicon.try_alias(yes, "no"),
+            Err(LexiconError::AlreadyInterned(1))
+        );
+
+        let checkpoint = lexicon.checkpoint();
+        lexicon.alias(no, "nope");
+        lexicon.truncate_to(checkpoint);
+        assert_eq!(lexicon.get("nope"), None);
+        assert_eq!(lexicon.spellings_of(no).count(), 1);
+    }
+}
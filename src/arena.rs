@@ -0,0 +1,442 @@
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::mem;
+
+use crate::Backend;
+
+/// Append-only string storage backing the interners (and the default
+/// `Backend` of a `Lexicon`, exported as `BucketBackend`).
+///
+/// Strings are copied into a list of fixed-size chunks, which are allocated
+/// up front and only ever filled up to their capacity, so no string is ever
+/// moved once stored. A string which doesn't fit into the last chunk starts
+/// a new one (or gets a chunk of its own, if longer than `CHUNK_SIZE`).
+///
+/// Stored strings are referred to by their `Span`, i.e., the index of their
+/// chunk along with their position within it, and are resolved through
+/// `Arena::get`.
+///
+/// `'static` and owned strings are not copied at all: rather, the references
+/// (or allocations) themselves are stored in `external`, with their spans
+/// marked by the `EXTERNAL` chunk index.
+///
+/// Chunks are reference counted, such that cloning an arena shares (rather
+/// than copies) its contents, with every span handed out by the original
+/// remaining valid for the clone as well. The last chunk is only written to
+/// while it is not shared; otherwise, the next allocation starts a new one.
+#[derive(Clone, Debug)]
+pub struct Arena {
+    /// Only ever holds valid UTF-8, as only `str`s are stored.
+    bytes: ByteArena,
+}
+
+/// Byte storage underlying `Arena`, storing arbitrary byte strings in the
+/// very same way. Used directly by `ByteLexicon`.
+#[derive(Clone, Debug)]
+pub(crate) struct ByteArena {
+    /// Never empty: the last chunk is the one currently being filled.
+    chunks: Vec<Arc<Vec<u8>>>,
+    external: Vec<External>,
+}
+
+/// A byte string stored by a `ByteArena` without being copied into its
+/// chunks.
+#[derive(Clone, Debug)]
+enum External {
+    Static(&'static [u8]),
+    Owned(Arc<Vec<u8>>),
+}
+
+/// Location of a string stored in an `Arena`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span {
+    chunk: u32,
+    start: u32,
+    len: u32,
+}
+
+/// Position within an `Arena`, which the arena may later be truncated back to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Mark {
+    chunk: usize,
+    len: usize,
+    external: usize,
+}
+
+impl Arena {
+    /// Capacity of every chunk but the first (whose capacity is given to
+    /// `Arena::with_capacity`), and those holding a single longer string.
+    pub const CHUNK_SIZE: usize = 4096;
+}
+
+impl ByteArena {
+    /// Maximum length of a single stored byte string.
+    pub const MAX_LEN: usize = u32::MAX as usize;
+
+    /// Chunk index of the spans of external strings, whose `start` is their
+    /// index within `external` instead.
+    const EXTERNAL: u32 = u32::MAX;
+
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            chunks: alloc::vec![Arc::new(Vec::with_capacity(cap))],
+            external: Vec::new(),
+        }
+    }
+
+    fn alloc_external(&mut self, bytes: External) -> Span {
+        let len = match &bytes {
+            External::Static(bytes) => bytes.len(),
+            External::Owned(bytes) => bytes.len(),
+        };
+        assert!(len <= Self::MAX_LEN, "string is too long to store");
+        self.external.push(bytes);
+        Span {
+            chunk: Self::EXTERNAL,
+            start: (self.external.len() - 1) as u32,
+            len: len as u32,
+        }
+    }
+
+    /// Copies `bytes` into the arena, returning their span.
+    ///
+    /// # Panics
+    /// Panics if `bytes` is longer than `ByteArena::MAX_LEN`.
+    pub fn alloc(&mut self, bytes: &[u8]) -> Span {
+        assert!(bytes.len() <= Self::MAX_LEN, "string is too long to store");
+        let fits = |chunk: &mut Arc<Vec<u8>>| {
+            chunk.capacity() - chunk.len() >= bytes.len() && Arc::get_mut(chunk).is_some()
+        };
+        if !self.chunks.last_mut().is_some_and(fits) {
+            let cap = Arena::CHUNK_SIZE.max(bytes.len());
+            #[cfg(feature = "tracing")]
+            tracing::debug!(chunk = self.chunks.len(), cap, "allocating arena chunk");
+            self.chunks.push(Arc::new(Vec::with_capacity(cap)));
+        }
+
+        let idx = self.chunks.len() - 1;
+        let chunk = Arc::get_mut(&mut self.chunks[idx]).expect("arena chunk is shared");
+        let start = chunk.len();
+        // never exceeds the chunk's capacity, so nothing already stored moves
+        chunk.extend_from_slice(bytes);
+        Span {
+            chunk: idx as u32,
+            start: start as u32,
+            len: bytes.len() as u32,
+        }
+    }
+
+    pub fn alloc_static(&mut self, bytes: &'static [u8]) -> Span {
+        self.alloc_external(External::Static(bytes))
+    }
+
+    /// Stores `bytes` as is, without copying its contents (nor shrinking its
+    /// allocation).
+    pub fn alloc_owned(&mut self, bytes: Vec<u8>) -> Span {
+        self.alloc_external(External::Owned(Arc::new(bytes)))
+    }
+
+    /// Returns the bytes stored at `span`.
+    ///
+    /// # Panics
+    /// Panics if `span` does not lie within the arena, e.g., if it was since
+    /// truncated away. A span handed out by another arena may instead lie
+    /// within this one, and resolve to whichever bytes it covers here.
+    pub fn get(&self, span: Span) -> &[u8] {
+        let start = span.start as usize;
+        match span.chunk {
+            Self::EXTERNAL => match &self.external[start] {
+                External::Static(bytes) => bytes,
+                External::Owned(bytes) => bytes,
+            },
+            chunk => &self.chunks[chunk as usize][start..start + span.len as usize],
+        }
+    }
+
+    /// Empties the arena, keeping only its last chunk.
+    /// Every span previously handed out by the arena is invalidated.
+    pub fn clear(&mut self) {
+        let mut last = self.chunks.pop().expect("arena has no chunks");
+        match Arc::get_mut(&mut last) {
+            Some(chunk) => chunk.clear(),
+            None => last = Arc::new(Vec::with_capacity(last.capacity())),
+        }
+        self.chunks.clear();
+        self.chunks.push(last);
+        self.external.clear();
+    }
+
+    pub fn mark(&self) -> Mark {
+        let chunk = self.chunks.len() - 1;
+        Mark {
+            chunk,
+            len: self.chunks[chunk].len(),
+            external: self.external.len(),
+        }
+    }
+
+    /// Whether `mark` is a position within the arena's current contents, with
+    /// `span` allocated before it.
+    pub fn precedes(&self, span: Span, mark: Mark) -> bool {
+        let len = match self.chunks.get(mark.chunk) {
+            Some(chunk) => chunk.len(),
+            None => return false,
+        };
+        if mark.len > len || mark.external > self.external.len() {
+            return false;
+        }
+        if span.chunk == Self::EXTERNAL {
+            return (span.start as usize) < mark.external;
+        }
+        let (chunk, end) = (span.chunk as usize, span.start as usize + span.len as usize);
+        chunk < mark.chunk || chunk == mark.chunk && end <= mark.len
+    }
+
+    /// Truncates the arena back to `mark`, dropping every chunk started
+    /// since then, and invalidating every span handed out since `mark` was
+    /// taken. Chunks shared with clones of the arena are left as they are,
+    /// as the clones may still reference their contents.
+    pub fn truncate(&mut self, mark: Mark) {
+        self.chunks.truncate(mark.chunk + 1);
+        if let Some(chunk) = self.chunks.last_mut().and_then(Arc::get_mut) {
+            chunk.truncate(mark.len);
+        }
+        self.external.truncate(mark.external);
+    }
+
+    /// Whether `span` lies within the arena's current contents.
+    pub fn owns(&self, span: Span) -> bool {
+        if span.chunk == Self::EXTERNAL {
+            return (span.start as usize) < self.external.len();
+        }
+        match self.chunks.get(span.chunk as usize) {
+            Some(chunk) => span.start as usize + span.len as usize <= chunk.len(),
+            None => false,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.chunks.last().map_or(0, |chunk| chunk.capacity())
+    }
+
+    /// Makes room for `additional` more bytes within the last chunk,
+    /// starting a new one of (at least) that size if needed, and leaving
+    /// the rest of the current one unused.
+    pub fn reserve(&mut self, additional: usize) {
+        let last = self.chunks.last_mut().expect("arena has no chunks");
+        let fits = last.capacity() - last.len() >= additional && Arc::get_mut(last).is_some();
+        if additional > 0 && !fits {
+            let cap = Arena::CHUNK_SIZE.max(additional);
+            self.chunks.push(Arc::new(Vec::with_capacity(cap)));
+        }
+    }
+
+    /// Returns the number of bytes allocated for the last chunk, and for
+    /// every other chunk along with the owned external strings.
+    pub fn allocated(&self) -> (usize, usize) {
+        let retired = self
+            .chunks
+            .iter()
+            .rev()
+            .skip(1)
+            .map(|chunk| chunk.capacity());
+        let external = self.external.iter().map(|external| match external {
+            External::Static(_) => 0,
+            External::Owned(bytes) => bytes.capacity(),
+        });
+        (self.capacity(), retired.chain(external).sum())
+    }
+
+    /// Copies the bytes at `spans` into as few chunks as possible, each
+    /// allocated to fit, updating the spans in place and dropping every
+    /// other chunk. External byte strings stay where they are, though owned
+    /// ones are shrunk to fit as well (unless shared with a clone).
+    pub fn shrink_to_fit<'a>(&mut self, spans: impl IntoIterator<Item = &'a mut Span>) {
+        let mut spans = spans
+            .into_iter()
+            .filter(|span| span.chunk != Self::EXTERNAL)
+            .collect::<Vec<_>>();
+        // offsets within a chunk are limited to `u32`, so very large contents
+        // are split over several chunks
+        let mut sizes = alloc::vec![0];
+        for span in &spans {
+            let size = sizes.last_mut().unwrap();
+            match *size + span.len as usize > Self::MAX_LEN {
+                true => sizes.push(span.len as usize),
+                false => *size += span.len as usize,
+            }
+        }
+
+        let chunks = sizes.iter().map(|&size| Arc::new(Vec::with_capacity(size)));
+        let old = mem::replace(&mut self.chunks, chunks.collect());
+        let mut idx = 0;
+        for span in &mut spans {
+            let start = span.start as usize;
+            let bytes = &old[span.chunk as usize][start..start + span.len as usize];
+            if self.chunks[idx].len() + bytes.len() > sizes[idx] {
+                idx += 1;
+            }
+            let chunk = Arc::get_mut(&mut self.chunks[idx]).expect("arena chunk is shared");
+            **span = Span {
+                chunk: idx as u32,
+                start: chunk.len() as u32,
+                len: span.len,
+            };
+            chunk.extend_from_slice(bytes);
+        }
+
+        for external in &mut self.external {
+            if let External::Owned(bytes) = external {
+                if let Some(bytes) = Arc::get_mut(bytes) {
+                    bytes.shrink_to_fit();
+                }
+            }
+        }
+        self.external.shrink_to_fit();
+    }
+}
+
+impl Backend for Arena {
+    type Span = Span;
+    type Mark = Mark;
+
+    /// Maximum length of a single stored string. Strings (and their offsets
+    /// within a chunk) are limited to `u32`, keeping spans compact.
+    const MAX_LEN: usize = ByteArena::MAX_LEN;
+
+    fn with_capacity(cap: usize) -> Self {
+        Self {
+            bytes: ByteArena::with_capacity(cap),
+        }
+    }
+
+    /// Copies `string` into the arena, returning its span.
+    ///
+    /// # Panics
+    /// Panics if `string` is longer than `Arena::MAX_LEN`.
+    fn alloc(&mut self, string: &str) -> Span {
+        self.bytes.alloc(string.as_bytes())
+    }
+
+    fn alloc_static(&mut self, string: &'static str) -> Span {
+        self.bytes.alloc_static(string.as_bytes())
+    }
+
+    /// Stores `string` as is, without copying its contents (nor shrinking
+    /// its allocation).
+    fn alloc_owned(&mut self, string: String) -> Span {
+        self.bytes.alloc_owned(string.into_bytes())
+    }
+
+    /// Returns the string stored at `span`.
+    ///
+    /// # Panics
+    /// Panics if `span` does not lie within the arena, or does not cover a
+    /// valid UTF-8 string, e.g., if it was since truncated away, or handed
+    /// out by another arena (which may otherwise resolve to an unrelated
+    /// string).
+    fn get(&self, span: Span) -> &str {
+        core::str::from_utf8(self.bytes.get(span)).expect("span does not belong to this arena")
+    }
+
+    /// Empties the arena, keeping only its last chunk.
+    /// Every span previously handed out by the arena is invalidated.
+    fn clear(&mut self) {
+        self.bytes.clear()
+    }
+
+    fn mark(&self) -> Mark {
+        self.bytes.mark()
+    }
+
+    fn precedes(&self, span: Span, mark: Mark) -> bool {
+        self.bytes.precedes(span, mark)
+    }
+
+    fn truncate(&mut self, mark: Mark) {
+        self.bytes.truncate(mark)
+    }
+
+    fn owns(&self, span: Span) -> bool {
+        self.bytes.owns(span)
+    }
+
+    fn capacity(&self) -> usize {
+        self.bytes.capacity()
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.bytes.reserve(additional)
+    }
+
+    fn allocated(&self) -> (usize, usize) {
+        self.bytes.allocated()
+    }
+
+    fn shrink_to_fit<'a>(&mut self, spans: impl IntoIterator<Item = &'a mut Span>) {
+        self.bytes.shrink_to_fit(spans)
+    }
+}
+
+impl Arena {
+    /// Copies `string` into the arena, returning a reference to the copy.
+    ///
+    /// # Safety
+    /// The returned reference is only valid for as long as the arena (or any
+    /// of its clones) lives, and up until the arena is cleared or truncated.
+    /// Callers must ensure it does not escape beyond that.
+    #[cfg(feature = "std")]
+    pub(crate) unsafe fn alloc_stable(&mut self, string: &str) -> &'static str {
+        let span = self.alloc(string);
+        // chunks are never reallocated, so their contents never move
+        &*(self.get(span) as *const str)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_spans() {
+        let mut arena = Arena::with_capacity(4);
+        let first = arena.alloc("abc");
+        let mark = arena.mark();
+        let spans = ["defgh", "", "ijklmnop"].map(|s| arena.alloc(s));
+        assert_eq!(arena.get(first), "abc");
+        assert_eq!(spans.map(|s| arena.get(s)), ["defgh", "", "ijklmnop"]);
+        assert!(arena.precedes(first, mark));
+        assert!(!arena.precedes(spans[2], mark));
+
+        arena.truncate(mark);
+        assert!(arena.owns(first));
+        assert!(!arena.owns(spans[2]));
+        let reused = arena.alloc("xyz");
+        assert_eq!(arena.get(reused), "xyz");
+    }
+
+    #[test]
+    #[should_panic = "span does not belong to this arena"]
+    fn test_foreign_span() {
+        let (mut a, mut b) = (Arena::with_capacity(4), Arena::with_capacity(4));
+        let span = a.alloc("ab");
+        b.alloc("a\u{e9}");
+        b.get(span);
+    }
+
+    #[test]
+    fn test_stable_chunks() {
+        let mut arena = Arena::with_capacity(0);
+        let first = arena.alloc("first");
+        let ptr = arena.get(first).as_ptr();
+        for _ in 0..Arena::CHUNK_SIZE {
+            arena.alloc("filler");
+        }
+        let long = "x".repeat(Arena::CHUNK_SIZE * 2);
+        let span = arena.alloc(&long);
+        assert_eq!(arena.get(span), long);
+        assert_eq!(arena.get(first).as_ptr(), ptr);
+        assert!(arena.bytes.chunks.iter().all(|c| c.len() <= c.capacity()));
+    }
+}
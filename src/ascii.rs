@@ -0,0 +1,164 @@
+use crate::{Interner, Lexicon, LexiconError, Resolver, Symbol, Symbolic};
+
+/// Interner only accepting ASCII strings, e.g., for protocol keywords or
+/// header names. Interning a string containing any non-ASCII byte fails with
+/// `LexiconError::NonAscii`.
+///
+/// As every string is known to be ASCII, resolving a symbol to its bytes
+/// and comparing or case-converting strings never needs to consider UTF-8.
+#[derive(Clone, Debug, Default)]
+pub struct AsciiLexicon {
+    inner: Lexicon,
+}
+
+impl AsciiLexicon {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # Panics
+    /// Panics if `string` is not ASCII, see `try_intern`.
+    pub fn intern(&mut self, string: &str) -> Symbol {
+        match self.try_intern(string) {
+            Ok(id) => id,
+            Err(e) => panic!("{} while interning `{}`", e, string),
+        }
+    }
+
+    /// Like `intern`, but returns an error rather than panicking if `string`
+    /// is not ASCII (`LexiconError::NonAscii`), or is new and cannot be
+    /// interned (see `Lexicon::try_intern`).
+    pub fn try_intern(&mut self, string: &str) -> Result<Symbol, LexiconError> {
+        if !string.is_ascii() {
+            return Err(LexiconError::NonAscii);
+        }
+        self.inner.try_intern(string)
+    }
+
+    /// Interns the ASCII string `bytes`, failing with `LexiconError::NonAscii`
+    /// if any of them is not ASCII.
+    pub fn try_intern_bytes(&mut self, bytes: &[u8]) -> Result<Symbol, LexiconError> {
+        match core::str::from_utf8(bytes) {
+            Ok(string) => self.try_intern(string),
+            Err(_) => Err(LexiconError::NonAscii),
+        }
+    }
+
+    pub fn get(&self, string: &str) -> Option<Symbol> {
+        self.inner.get(string)
+    }
+
+    pub fn lookup(&self, id: Symbol) -> &str {
+        self.inner.lookup(id)
+    }
+
+    pub fn try_lookup(&self, id: Symbol) -> Option<&str> {
+        self.inner.try_lookup(id)
+    }
+
+    /// Returns the (ASCII) bytes of the string of `id`.
+    pub fn lookup_bytes(&self, id: Symbol) -> &[u8] {
+        self.lookup(id).as_bytes()
+    }
+
+    /// Whether the strings of `a` and `b` are equal, ignoring ASCII case.
+    pub fn eq_ignore_case(&self, a: Symbol, b: Symbol) -> bool {
+        a == b
+            || self
+                .lookup_bytes(a)
+                .eq_ignore_ascii_case(self.lookup_bytes(b))
+    }
+
+    /// Returns the symbol of the lowercase form of the string of `id`,
+    /// interning it if needed.
+    pub fn to_lowercase(&mut self, id: Symbol) -> Symbol {
+        let string = self.lookup(id);
+        if !string.bytes().any(|b| b.is_ascii_uppercase()) {
+            return id;
+        }
+        let lower = string.to_ascii_lowercase();
+        self.inner.intern_owned(lower)
+    }
+
+    /// Returns the symbol of the uppercase form of the string of `id`,
+    /// interning it if needed.
+    pub fn to_uppercase(&mut self, id: Symbol) -> Symbol {
+        let string = self.lookup(id);
+        if !string.bytes().any(|b| b.is_ascii_lowercase()) {
+            return id;
+        }
+        let upper = string.to_ascii_uppercase();
+        self.inner.intern_owned(upper)
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns the underlying `Lexicon`.
+    pub fn into_lexicon(self) -> Lexicon {
+        self.inner
+    }
+}
+
+impl<S: Symbolic> core::ops::Index<S> for AsciiLexicon {
+    type Output = str;
+
+    fn index(&self, index: S) -> &Self::Output {
+        self.lookup(index.get_symbol())
+    }
+}
+
+impl Resolver for AsciiLexicon {
+    fn resolve(&self, id: Symbol) -> &str {
+        self.lookup(id)
+    }
+
+    fn try_resolve(&self, id: Symbol) -> Option<&str> {
+        self.try_lookup(id)
+    }
+}
+
+impl Interner for AsciiLexicon {
+    type Error = LexiconError;
+
+    fn try_intern(&mut self, string: &str) -> Result<Symbol, LexiconError> {
+        self.try_intern(string)
+    }
+
+    fn get(&self, string: &str) -> Option<Symbol> {
+        self.get(string)
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ascii() {
+        let mut lexicon = AsciiLexicon::new();
+        let header = lexicon.intern("Content-Type");
+        let lower = lexicon.try_intern_bytes(b"content-type").unwrap();
+        assert!(lexicon.eq_ignore_case(header, lower));
+        assert_eq!(lexicon.to_lowercase(header), lower);
+        assert_eq!(lexicon.to_lowercase(lower), lower);
+        let upper = lexicon.to_uppercase(lower);
+        assert_eq!(lexicon.lookup(upper), "CONTENT-TYPE");
+
+        assert_eq!(lexicon.try_intern("naïve"), Err(LexiconError::NonAscii));
+        assert_eq!(
+            lexicon.try_intern_bytes(b"\xff"),
+            Err(LexiconError::NonAscii)
+        );
+        assert_eq!(lexicon.len(), 3);
+    }
+}
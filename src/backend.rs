@@ -0,0 +1,515 @@
+//! Storage backends of a `Lexicon`, selected through its last type
+//! parameter, e.g., `Lexicon<Symbol, DefaultHashBuilder, BufferBackend>`.
+//!
+//! * `BucketBackend` (the default) stores strings in a list of fixed-size
+//!   chunks, which are never moved once allocated, and are shared between
+//!   clones of a `Lexicon`.
+//! * `BufferBackend` stores every string in a single contiguous buffer, which
+//!   is reallocated as it grows. Strings are laid out more compactly, at the
+//!   cost of copying them on growth (as well as when cloning).
+//! * `LeakedBackend` leaks every string into its own `'static` allocation,
+//!   which is never freed, not even when the `Lexicon` is cleared.
+//! * `MmapBackend` (with the `mmap` feature) stores every string in a single
+//!   memory mapping, either anonymous or backed by a file, which is remapped
+//!   as it grows.
+//!
+//! Read-only lexicons encoded by `Lexicon::write_to` are mapped by
+//! `MappedLexicon` instead.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::mem;
+
+pub use crate::arena::Arena as BucketBackend;
+
+/// Storage for the strings of a `Lexicon`. Strings are never removed one by
+/// one: they may only be discarded all at once (`clear`), or back to a
+/// previously taken `mark` (`truncate`).
+pub trait Backend {
+    /// Handle of a stored string, from which it can be resolved again.
+    type Span: Copy + Debug;
+    /// Position within the backend, which it may be truncated back to.
+    type Mark: Copy + Debug + Eq;
+
+    /// Maximum length of a single stored string.
+    const MAX_LEN: usize;
+
+    /// Creates an empty backend, with room for (at least) `cap` bytes.
+    fn with_capacity(cap: usize) -> Self;
+
+    /// Stores a copy of `string`, which must be no longer than `MAX_LEN`.
+    fn alloc(&mut self, string: &str) -> Self::Span;
+
+    /// Stores `string`, which must be no longer than `MAX_LEN`. Backends
+    /// able to refer to `'static` strings directly should do so rather than
+    /// copying them, which is what this does by default.
+    fn alloc_static(&mut self, string: &'static str) -> Self::Span {
+        self.alloc(string)
+    }
+
+    /// Stores `string`, which must be no longer than `MAX_LEN`. Backends
+    /// able to take over its allocation should do so rather than copying
+    /// it, which is what this does by default.
+    fn alloc_owned(&mut self, string: String) -> Self::Span {
+        self.alloc(&string)
+    }
+
+    /// Returns the string stored at `span`.
+    fn get(&self, span: Self::Span) -> &str;
+
+    /// Discards every stored string, invalidating their spans.
+    fn clear(&mut self);
+
+    fn mark(&self) -> Self::Mark;
+
+    /// Whether `mark` is a position within the current contents, with `span`
+    /// stored before it.
+    fn precedes(&self, span: Self::Span, mark: Self::Mark) -> bool;
+
+    /// Discards every string stored since `mark` was taken, invalidating
+    /// their spans.
+    fn truncate(&mut self, mark: Self::Mark);
+
+    /// Whether `span` refers to a currently stored string.
+    fn owns(&self, span: Self::Span) -> bool;
+
+    fn capacity(&self) -> usize;
+
+    /// Makes room for strings of at least `additional` bytes in total to be
+    /// stored without allocating, for backends allocating up front. Does
+    /// nothing by default.
+    fn reserve(&mut self, additional: usize) {
+        let _ = additional;
+    }
+
+    /// Returns the number of bytes allocated for the buffer strings are
+    /// currently stored into, and for every other buffer (if any), e.g., for
+    /// `Lexicon::memory_usage`.
+    fn allocated(&self) -> (usize, usize) {
+        (self.capacity(), 0)
+    }
+
+    /// Moves the strings at `spans` (which must be every span still in use)
+    /// into storage allocated to fit, updating the spans in place, and
+    /// releases any other memory. Every other span, and every mark, is
+    /// invalidated.
+    ///
+    /// By default, copies the strings into a new backend of the exact size
+    /// needed.
+    fn shrink_to_fit<'a>(&mut self, spans: impl IntoIterator<Item = &'a mut Self::Span>)
+    where
+        Self: Sized,
+        Self::Span: 'a,
+    {
+        let mut spans = spans.into_iter().collect::<Vec<_>>();
+        let len = spans.iter().map(|span| self.get(**span).len()).sum();
+        let old = mem::replace(self, Self::with_capacity(len));
+        for span in &mut spans {
+            **span = self.alloc(old.get(**span));
+        }
+    }
+}
+
+/// Backend storing every string in a single, contiguous buffer.
+#[derive(Clone, Debug, Default)]
+pub struct BufferBackend {
+    buf: String,
+}
+
+/// Location of a string stored in a `BufferBackend`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferSpan {
+    start: usize,
+    end: usize,
+}
+
+impl Backend for BufferBackend {
+    type Span = BufferSpan;
+    type Mark = usize;
+
+    const MAX_LEN: usize = isize::MAX as usize;
+
+    fn with_capacity(cap: usize) -> Self {
+        Self {
+            buf: String::with_capacity(cap),
+        }
+    }
+
+    fn alloc(&mut self, string: &str) -> BufferSpan {
+        #[cfg(feature = "tracing")]
+        let _grow = (self.buf.capacity() - self.buf.len() < string.len()).then(|| {
+            tracing::debug_span!("lexicon_buffer_growth", cap = self.buf.capacity()).entered()
+        });
+        let start = self.buf.len();
+        self.buf.push_str(string);
+        BufferSpan {
+            start,
+            end: self.buf.len(),
+        }
+    }
+
+    fn get(&self, span: BufferSpan) -> &str {
+        &self.buf[span.start..span.end]
+    }
+
+    fn clear(&mut self) {
+        self.buf.clear()
+    }
+
+    fn mark(&self) -> usize {
+        self.buf.len()
+    }
+
+    fn precedes(&self, span: BufferSpan, mark: usize) -> bool {
+        span.end <= mark && mark <= self.buf.len()
+    }
+
+    fn truncate(&mut self, mark: usize) {
+        self.buf.truncate(mark)
+    }
+
+    fn owns(&self, span: BufferSpan) -> bool {
+        span.end <= self.buf.len()
+    }
+
+    fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.buf.reserve(additional)
+    }
+}
+
+/// Backend leaking every string into its own `'static` allocation. Useful
+/// for process-lifetime interners, whose strings would never be freed
+/// anyway; clearing or truncating only ever forgets strings.
+#[derive(Clone, Debug, Default)]
+pub struct LeakedBackend {
+    // number of strings stored so far, never decreasing (not even when
+    // truncating), such that spans of discarded strings never precede a
+    // mark taken since
+    count: usize,
+}
+
+/// String stored in a `LeakedBackend`, along with its position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LeakedSpan {
+    string: &'static str,
+    seq: usize,
+}
+
+impl LeakedBackend {
+    fn push(&mut self, string: &'static str) -> LeakedSpan {
+        self.count += 1;
+        LeakedSpan {
+            string,
+            seq: self.count - 1,
+        }
+    }
+}
+
+impl Backend for LeakedBackend {
+    type Span = LeakedSpan;
+    type Mark = usize;
+
+    const MAX_LEN: usize = isize::MAX as usize;
+
+    fn with_capacity(_: usize) -> Self {
+        Self::default()
+    }
+
+    fn alloc(&mut self, string: &str) -> LeakedSpan {
+        self.push(Box::leak(Box::from(string)))
+    }
+
+    fn alloc_static(&mut self, string: &'static str) -> LeakedSpan {
+        self.push(string)
+    }
+
+    fn alloc_owned(&mut self, string: String) -> LeakedSpan {
+        self.push(Box::leak(string.into_boxed_str()))
+    }
+
+    fn get(&self, span: LeakedSpan) -> &str {
+        span.string
+    }
+
+    fn clear(&mut self) {}
+
+    fn mark(&self) -> usize {
+        self.count
+    }
+
+    fn precedes(&self, span: LeakedSpan, mark: usize) -> bool {
+        span.seq < mark && mark <= self.count
+    }
+
+    fn truncate(&mut self, _: usize) {}
+
+    // leaked strings stay valid forever
+    fn owns(&self, _: LeakedSpan) -> bool {
+        true
+    }
+
+    fn capacity(&self) -> usize {
+        0
+    }
+
+    // leaked strings can never be freed, so copying them would only leak more
+    fn shrink_to_fit<'a>(&mut self, _: impl IntoIterator<Item = &'a mut LeakedSpan>) {}
+}
+
+/// Backend storing every string in a single, contiguous memory mapping,
+/// which is remapped (to at least twice its size) as it grows. Created by
+/// `with_capacity`, the mapping is anonymous; created by `create`, it is
+/// backed by a file, letting the operating system page strings out to it
+/// rather than to swap, e.g., for lexicons larger than memory.
+///
+/// # Panics
+/// Storing strings panics if the mapping can't be grown (e.g., if the disk
+/// backing its file is full), just as other backends abort when running out
+/// of memory.
+#[cfg(feature = "mmap")]
+#[derive(Debug)]
+pub struct MmapBackend {
+    map: memmap2::MmapMut,
+    len: usize,
+    file: Option<std::fs::File>,
+}
+
+#[cfg(feature = "mmap")]
+impl MmapBackend {
+    /// Size mappings are never made smaller than (when growing), i.e., that
+    /// of a page on most platforms.
+    const MIN_CAPACITY: usize = 4096;
+
+    /// Creates an empty backend mapping the file at `path`, which is created
+    /// if need be, and truncated otherwise. Strings are stored at the start
+    /// of the file, which is grown along with the mapping, so that its
+    /// unused end is filled with zeroes.
+    ///
+    /// The file must not be modified by anything else for as long as the
+    /// backend is alive, as doing so would change the strings out from under
+    /// any references previously resolved from it.
+    pub fn create(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.set_len(Self::MIN_CAPACITY as u64)?;
+        // see above regarding modifications to the file
+        let map = unsafe { memmap2::MmapMut::map_mut(&file)? };
+        Ok(Self {
+            map,
+            len: 0,
+            file: Some(file),
+        })
+    }
+
+    /// Flushes the stored strings to the backing file (if any).
+    pub fn flush(&self) -> std::io::Result<()> {
+        self.map.flush()
+    }
+
+    /// Remaps the backend to `cap` bytes, which must be no fewer than are
+    /// stored.
+    fn remap(&mut self, cap: usize) -> std::io::Result<()> {
+        match &self.file {
+            Some(file) => {
+                // the file is grown before being mapped, but only shrunk
+                // once no longer mapped past its new end
+                let grow = cap > self.map.len();
+                if grow {
+                    file.set_len(cap as u64)?;
+                }
+                // see `create` regarding modifications to the file
+                self.map = unsafe { memmap2::MmapOptions::new().len(cap).map_mut(file)? };
+                if !grow {
+                    file.set_len(cap as u64)?;
+                }
+                Ok(())
+            }
+            None => {
+                let mut map = memmap2::MmapMut::map_anon(cap)?;
+                map[..self.len].copy_from_slice(&self.map[..self.len]);
+                self.map = map;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl Backend for MmapBackend {
+    type Span = BufferSpan;
+    type Mark = usize;
+
+    const MAX_LEN: usize = isize::MAX as usize;
+
+    fn with_capacity(cap: usize) -> Self {
+        let map =
+            memmap2::MmapMut::map_anon(cap.max(Self::MIN_CAPACITY)).expect("failed to map memory");
+        Self {
+            map,
+            len: 0,
+            file: None,
+        }
+    }
+
+    fn alloc(&mut self, string: &str) -> BufferSpan {
+        self.reserve(string.len());
+        let start = self.len;
+        self.len += string.len();
+        self.map[start..self.len].copy_from_slice(string.as_bytes());
+        BufferSpan {
+            start,
+            end: self.len,
+        }
+    }
+
+    /// Returns the string stored at `span`.
+    ///
+    /// # Panics
+    /// Panics if `span` does not lie within the stored strings, or does not
+    /// cover a valid UTF-8 string, e.g., if it was handed out by another
+    /// backend.
+    fn get(&self, span: BufferSpan) -> &str {
+        core::str::from_utf8(&self.map[..self.len][span.start..span.end])
+            .expect("span does not belong to this backend")
+    }
+
+    fn clear(&mut self) {
+        self.len = 0
+    }
+
+    fn mark(&self) -> usize {
+        self.len
+    }
+
+    fn precedes(&self, span: BufferSpan, mark: usize) -> bool {
+        span.end <= mark && mark <= self.len
+    }
+
+    fn truncate(&mut self, mark: usize) {
+        self.len = self.len.min(mark)
+    }
+
+    fn owns(&self, span: BufferSpan) -> bool {
+        span.end <= self.len
+    }
+
+    fn capacity(&self) -> usize {
+        self.map.len()
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        let needed = self.len.checked_add(additional).expect("capacity overflow");
+        if needed > self.map.len() {
+            #[cfg(feature = "tracing")]
+            let _grow = tracing::debug_span!("lexicon_mmap_growth", cap = self.map.len()).entered();
+            let cap = needed.max(self.map.len() * 2);
+            self.remap(cap).expect("failed to grow memory mapping");
+        }
+    }
+
+    /// Moves the strings at `spans` to the start of the mapping, in order,
+    /// and shrinks it (along with its file, if any) to fit them.
+    fn shrink_to_fit<'a>(&mut self, spans: impl IntoIterator<Item = &'a mut BufferSpan>) {
+        let mut spans = spans.into_iter().collect::<Vec<_>>();
+        spans.sort_unstable_by_key(|span| span.start);
+        // strings are only ever moved towards the start, so none is
+        // overwritten before being moved itself, while spans shared by
+        // several keys are only moved once
+        let (mut len, mut last) = (0, None);
+        for span in spans {
+            let old = *span;
+            *span = match last {
+                Some((prev, new)) if prev == old => new,
+                _ => {
+                    self.map.copy_within(old.start..old.end, len);
+                    len += old.end - old.start;
+                    BufferSpan {
+                        start: len - (old.end - old.start),
+                        end: len,
+                    }
+                }
+            };
+            last = Some((old, *span));
+        }
+        self.len = len;
+        self.remap(len.max(Self::MIN_CAPACITY))
+            .expect("failed to shrink memory mapping");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lexicon, LexiconError};
+
+    #[test]
+    fn test_backends() {
+        fn exercise<B: Backend>(
+            mut lexicon: Lexicon<crate::Symbol, hashbrown::DefaultHashBuilder, B>,
+        ) {
+            let a = lexicon.intern("a");
+            let reserved = lexicon.reserve_symbol();
+            let checkpoint = lexicon.checkpoint();
+            let b = lexicon.intern("bb");
+            lexicon.define(reserved, "r");
+            let later = lexicon.checkpoint();
+            assert_eq!(lexicon.lookup(a), "a");
+            assert_eq!(lexicon.lookup(b), "bb");
+            lexicon.truncate_to(checkpoint);
+            assert_eq!(lexicon.get("bb"), None);
+            assert!(lexicon.is_reserved(reserved));
+            assert_eq!(lexicon.intern("a much longer string").as_usize(), 2);
+            assert_eq!(
+                lexicon.try_truncate_to(later),
+                Err(LexiconError::StaleCheckpoint)
+            );
+            lexicon.validate();
+        }
+
+        exercise(Lexicon::with_backend(BucketBackend::with_capacity(1)));
+        exercise(Lexicon::with_backend(BufferBackend::default()));
+        exercise(Lexicon::with_backend(LeakedBackend::default()));
+        #[cfg(feature = "mmap")]
+        exercise(Lexicon::with_backend(MmapBackend::with_capacity(0)));
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_mmap_backend() {
+        let path = std::env::temp_dir().join(format!("lexicon-{}.strings", std::process::id()));
+        let mut lexicon = Lexicon::with_backend(MmapBackend::create(&path).unwrap());
+        let syms = (0..10_000)
+            .map(|i| lexicon.intern(&format!("string {}", i)))
+            .collect::<Vec<_>>();
+        let checkpoint = lexicon.checkpoint();
+        lexicon.intern("discarded");
+        lexicon.truncate_to(checkpoint);
+        lexicon.alias(syms[0], "alias");
+
+        lexicon.shrink_to_fit();
+        lexicon.validate();
+        for (i, &sym) in syms.iter().enumerate() {
+            assert_eq!(lexicon.lookup(sym), format!("string {}", i));
+        }
+        assert_eq!(lexicon.get("alias"), Some(syms[0]));
+        let stored = (0..10_000)
+            .map(|i| format!("string {}", i).len())
+            .sum::<usize>();
+        assert_eq!(lexicon.backend.mark(), stored + "alias".len());
+        lexicon.backend.flush().unwrap();
+        let file_len = std::fs::metadata(&path).unwrap().len();
+        assert_eq!(file_len as usize, stored + "alias".len());
+        drop(lexicon);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,369 @@
+//! Compact binary encoding of a `Lexicon`, for persisting symbol tables (e.g.
+//! in incremental compilation caches) between runs.
+//!
+//! All integers are little-endian `u32`s. The layout is as follows:
+//!
+//! | field        | size              | description                              |
+//! |--------------|-------------------|------------------------------------------|
+//! | `magic`      | 4                 | the bytes `LXCN`                         |
+//! | `version`    | 4                 | format version, currently `2`            |
+//! | `byte_order` | 4                 | `BYTE_ORDER`, i.e., the bytes `04 03 02 01` |
+//! | `header_len` | 4                 | length of the header, currently `28`     |
+//! | `count`      | 4                 | number of symbols                        |
+//! | `checksum`   | 4                 | CRC-32 of `offsets`, `reserved` and `data` |
+//! | `reserved_count` | 4             | number of reserved symbols               |
+//! | `offsets`    | 4 × (`count` + 1) | byte offsets of each string in `data`    |
+//! | `reserved`   | 4 × `reserved_count` | reserved symbols, in ascending order  |
+//! | `data`       | `offsets[count]`  | concatenated string bytes                |
+//!
+//! String `i` (and therefore `Symbol` `i`) spans `data[offsets[i]..offsets[i + 1]]`.
+//! Symbols reserved but not defined yet (see `Lexicon::reserve_symbol`) span
+//! no bytes, and are listed in `reserved` instead, to be reserved again when
+//! read back.
+//!
+//! # Compatibility
+//!
+//! Readers reject encodings of versions newer than their own, as well as
+//! encodings whose `byte_order` does not match (e.g., byte-swapped ones).
+//! Later releases may extend the format compatibly, by appending fields to
+//! the header (growing `header_len`) or bytes after `data`, both of which
+//! readers skip, keeping the version as is. Any other change bumps the
+//! version.
+//!
+//! Encodings of version `1`, whose header ends right after `count` (at 12
+//! bytes), and which carry no checksum nor reserved symbols, are still read.
+
+use std::io::{self, Read, Write};
+
+use crate::Lexicon;
+
+/// Leading bytes of every encoded `Lexicon`.
+pub const MAGIC: [u8; 4] = *b"LXCN";
+
+/// Current version of the encoding.
+pub const VERSION: u32 = 2;
+
+/// Value of the `byte_order` field, which reads back differently if the
+/// encoding's bytes were swapped.
+pub const BYTE_ORDER: u32 = 0x0102_0304;
+
+/// Length of the header written by the current version, i.e., of every
+/// field preceding `offsets`.
+pub(crate) const HEADER_LEN: usize = 28;
+
+/// Length of the header of version `1`: `magic`, `version` and `count`.
+const V1_HEADER_LEN: usize = 12;
+
+pub(crate) fn invalid_data(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+/// Header of an encoded `Lexicon`.
+pub(crate) struct Header {
+    /// Length of the header, i.e., position of `offsets`.
+    pub len: usize,
+    pub count: usize,
+    /// `None` for encodings of version `1`.
+    pub checksum: Option<u32>,
+    /// Number of reserved symbols, listed right after `offsets`.
+    pub reserved: usize,
+}
+
+/// Reads and validates the header of an encoded `Lexicon`, leaving `reader`
+/// at the start of `offsets`.
+pub(crate) fn read_header(reader: &mut impl Read) -> io::Result<Header> {
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+    if magic != MAGIC {
+        return Err(invalid_data("not an encoded lexicon"));
+    }
+    let version = read_u32(reader)?;
+    if version == 1 {
+        let count = read_u32(reader)? as usize;
+        return Ok(Header {
+            len: V1_HEADER_LEN,
+            count,
+            checksum: None,
+            reserved: 0,
+        });
+    }
+    if version != VERSION {
+        return Err(invalid_data(format!(
+            "unsupported lexicon version {}",
+            version
+        )));
+    }
+
+    if read_u32(reader)? != BYTE_ORDER {
+        return Err(invalid_data("lexicon has the wrong byte order"));
+    }
+    let len = read_u32(reader)? as usize;
+    if len < HEADER_LEN {
+        return Err(invalid_data("lexicon header is too short"));
+    }
+    let count = read_u32(reader)? as usize;
+    let checksum = read_u32(reader)?;
+    let reserved = read_u32(reader)? as usize;
+    if reserved > count {
+        return Err(invalid_data(
+            "lexicon has more reserved symbols than symbols",
+        ));
+    }
+    // fields added by later releases
+    let extra = (len - HEADER_LEN) as u64;
+    if io::copy(&mut reader.take(extra), &mut io::sink())? != extra {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(Header {
+        len,
+        count,
+        checksum: Some(checksum),
+        reserved,
+    })
+}
+
+/// Table of the CRC-32 (as used by zlib, PNG, etc.) of every byte.
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Running CRC-32 checksum.
+#[derive(Clone, Copy)]
+pub(crate) struct Crc32(u32);
+
+impl Crc32 {
+    pub fn new() -> Self {
+        Crc32(!0)
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = CRC_TABLE[((self.0 ^ byte as u32) & 0xff) as usize] ^ (self.0 >> 8);
+        }
+    }
+
+    pub fn finish(self) -> u32 {
+        !self.0
+    }
+}
+
+impl Lexicon {
+    /// Writes the binary encoding of this `Lexicon` (described in the
+    /// `binary` module) to `writer`.
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        let total = self.iter().map(|(_, s)| s.len()).sum::<usize>();
+        if total > u32::MAX as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "lexicon is too large to encode",
+            ));
+        }
+
+        let mut offsets = Vec::with_capacity(4 * (self.symbol_count() + 1));
+        let mut reserved = Vec::new();
+        let mut offset = 0u32;
+        offsets.extend_from_slice(&offset.to_le_bytes());
+        for (i, string) in self.slots().enumerate() {
+            match string {
+                Some(string) => offset += string.len() as u32,
+                None => reserved.extend_from_slice(&(i as u32).to_le_bytes()),
+            }
+            offsets.extend_from_slice(&offset.to_le_bytes());
+        }
+        let mut crc = Crc32::new();
+        crc.update(&offsets);
+        crc.update(&reserved);
+        for (_, string) in self {
+            crc.update(string.as_bytes());
+        }
+
+        writer.write_all(&MAGIC)?;
+        for field in [
+            VERSION,
+            BYTE_ORDER,
+            HEADER_LEN as u32,
+            self.symbol_count() as u32,
+            crc.finish(),
+            (reserved.len() / 4) as u32,
+        ] {
+            writer.write_all(&field.to_le_bytes())?;
+        }
+        writer.write_all(&offsets)?;
+        writer.write_all(&reserved)?;
+        for (_, string) in self {
+            writer.write_all(string.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Reads a `Lexicon` previously written with `Lexicon::write_to` (by
+    /// this release or an earlier one), verifying its checksum. Every
+    /// `Symbol` of the written `Lexicon` is valid for the returned one.
+    pub fn read_from(mut reader: impl Read) -> io::Result<Self> {
+        let header = read_header(&mut reader)?;
+        let mut crc = Crc32::new();
+        let mut offsets = Vec::with_capacity(header.count.min(1 << 16) + 1);
+        for _ in 0..=header.count {
+            let offset = read_u32(&mut reader)?;
+            crc.update(&offset.to_le_bytes());
+            if offsets.last().map_or(offset != 0, |&last| offset < last) {
+                return Err(invalid_data("lexicon offsets are not ascending"));
+            }
+            offsets.push(offset);
+        }
+        let mut reserved = Vec::with_capacity(header.reserved.min(1 << 16));
+        for _ in 0..header.reserved {
+            let id = read_u32(&mut reader)?;
+            crc.update(&id.to_le_bytes());
+            if reserved.last().is_some_and(|&last| id <= last) || id as usize >= header.count {
+                return Err(invalid_data("lexicon reserved symbols are not ascending"));
+            }
+            reserved.push(id);
+        }
+        let total = offsets.last().copied().unwrap_or(0) as usize;
+        let mut data = Vec::new();
+        reader.by_ref().take(total as u64).read_to_end(&mut data)?;
+        if data.len() != total {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        crc.update(&data);
+        if header
+            .checksum
+            .is_some_and(|checksum| checksum != crc.finish())
+        {
+            return Err(invalid_data("lexicon checksum mismatch"));
+        }
+
+        let mut lexicon = Lexicon::with_capacity(total);
+        let mut reserved = reserved.into_iter().peekable();
+        for (i, span) in offsets.windows(2).enumerate() {
+            if reserved.next_if_eq(&(i as u32)).is_some() {
+                if span[0] != span[1] {
+                    return Err(invalid_data(format!("reserved symbol {} has a string", i)));
+                }
+                lexicon.reserve_symbol();
+                continue;
+            }
+            let bytes = &data[span[0] as usize..span[1] as usize];
+            let string = std::str::from_utf8(bytes)
+                .map_err(|e| invalid_data(format!("string {} is not UTF-8: {}", i, e)))?;
+            if lexicon.intern(string).as_usize() != i {
+                return Err(invalid_data(format!("duplicate string `{}`", string)));
+            }
+        }
+        Ok(lexicon)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_write_read() {
+        let mut lexicon = Lexicon::default();
+        let syms = ["use", "std", "", "io"].map(|s| lexicon.intern(s));
+
+        let mut bytes = Vec::new();
+        lexicon.write_to(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), HEADER_LEN + 4 * 5 + 8);
+
+        let restored = Lexicon::read_from(&bytes[..]).unwrap();
+        for sym in syms {
+            assert_eq!(lexicon.lookup(sym), restored.lookup(sym));
+        }
+    }
+
+    #[test]
+    fn test_write_read_reserved() {
+        let mut lexicon = Lexicon::default();
+        let a = lexicon.intern("a");
+        let reserved = [lexicon.reserve_symbol(), lexicon.reserve_symbol()];
+        let b = lexicon.intern("b");
+
+        let mut bytes = Vec::new();
+        lexicon.write_to(&mut bytes).unwrap();
+        let mut restored = Lexicon::read_from(&bytes[..]).unwrap();
+        assert_eq!((restored.lookup(a), restored.lookup(b)), ("a", "b"));
+        assert!(reserved.iter().all(|&id| restored.is_reserved(id)));
+        restored.define(reserved[1], "c");
+        restored.validate();
+    }
+
+    #[test]
+    fn test_read_invalid() {
+        let mut lexicon = Lexicon::default();
+        lexicon.intern("truncated");
+        let mut bytes = Vec::new();
+        lexicon.write_to(&mut bytes).unwrap();
+
+        assert!(Lexicon::read_from(&bytes[..bytes.len() - 1]).is_err());
+        bytes[0] = b'X';
+        assert!(Lexicon::read_from(&bytes[..]).is_err());
+    }
+
+    #[test]
+    fn test_integrity() {
+        let mut lexicon = Lexicon::default();
+        lexicon.intern("checked");
+        let mut bytes = Vec::new();
+        lexicon.write_to(&mut bytes).unwrap();
+
+        let mut corrupt = bytes.clone();
+        *corrupt.last_mut().unwrap() = b'D';
+        assert!(Lexicon::read_from(&corrupt[..]).is_err());
+        let mut swapped = bytes.clone();
+        swapped[8..12].reverse();
+        assert!(Lexicon::read_from(&swapped[..]).is_err());
+        let mut newer = bytes;
+        newer[4] = 3;
+        assert!(Lexicon::read_from(&newer[..]).is_err());
+    }
+
+    #[test]
+    fn test_compatibility() {
+        // version 1: no byte order, header length nor checksum
+        let mut v1 = Vec::new();
+        v1.extend_from_slice(&MAGIC);
+        for field in [1u32, 2, 0, 2, 4] {
+            v1.extend_from_slice(&field.to_le_bytes());
+        }
+        v1.extend_from_slice(b"abcd");
+        let lexicon = Lexicon::read_from(&v1[..]).unwrap();
+        assert_eq!(
+            lexicon.iter().map(|(_, s)| s).collect::<Vec<_>>(),
+            ["ab", "cd"]
+        );
+
+        // a later release appending a header field, and bytes after `data`
+        let mut bytes = Vec::new();
+        lexicon.write_to(&mut bytes).unwrap();
+        bytes[12..16].copy_from_slice(&(HEADER_LEN as u32 + 4).to_le_bytes());
+        bytes.splice(HEADER_LEN..HEADER_LEN, *b"EXTN");
+        bytes.extend_from_slice(b"trailer");
+        let read = Lexicon::read_from(&bytes[..]).unwrap();
+        assert_eq!(read.get("cd"), lexicon.get("cd"));
+    }
+}
@@ -0,0 +1,78 @@
+use core::marker::PhantomData;
+
+use crate::{Key, Lexicon, Symbol};
+
+/// `Symbol` branded with the (invariant) lifetime `'brand` of the `Lexicon`
+/// it was interned in. Every call to `Lexicon::branded` introduces a fresh
+/// brand, so resolving a `BrandedSymbol` against any other `Lexicon` fails
+/// to compile rather than silently returning the wrong string:
+///
+/// ```compile_fail
+/// use lexicon::Lexicon;
+///
+/// Lexicon::branded(|a| {
+///     Lexicon::branded(|b| {
+///         let sym = a.intern("a");
+///         b.lookup(sym)
+///     });
+/// });
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Debug)]
+pub struct BrandedSymbol<'brand> {
+    sym: Symbol,
+    brand: PhantomData<fn(&'brand ()) -> &'brand ()>,
+}
+
+impl BrandedSymbol<'_> {
+    /// Strips the brand off of this symbol, e.g., to store it beyond the
+    /// closure passed to `Lexicon::branded`.
+    pub fn unbrand(self) -> Symbol {
+        self.sym
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        self.sym.as_u32()
+    }
+
+    pub fn as_usize(&self) -> usize {
+        self.sym.as_usize()
+    }
+}
+
+impl Key for BrandedSymbol<'_> {
+    fn try_from_usize(idx: usize) -> Option<Self> {
+        Some(BrandedSymbol {
+            sym: Symbol::try_from_usize(idx)?,
+            brand: PhantomData,
+        })
+    }
+
+    fn to_usize(self) -> usize {
+        self.sym.as_usize()
+    }
+}
+
+impl Lexicon {
+    /// Runs `f` with a new, empty `Lexicon` whose symbols are branded with a
+    /// lifetime unique to this call, such that they may only ever be
+    /// resolved against that very `Lexicon`.
+    pub fn branded<R>(f: impl for<'brand> FnOnce(&mut Lexicon<BrandedSymbol<'brand>>) -> R) -> R {
+        f(&mut Lexicon::with_key_capacity(Lexicon::BASE_CAPACITY))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_branded() {
+        let sym = Lexicon::branded(|lexicon| {
+            let sym = lexicon.intern("branded");
+            assert_eq!(lexicon.intern("branded"), sym);
+            assert_eq!(lexicon.lookup(sym), "branded");
+            sym.unbrand()
+        });
+        assert_eq!(sym.as_u32(), 0);
+    }
+}
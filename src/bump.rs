@@ -0,0 +1,121 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use bumpalo::Bump;
+use hashbrown::{DefaultHashBuilder, HashTable};
+
+use crate::{Key, LexiconError, Symbol};
+
+/// String interner allocating its strings from a user-provided `Bump` arena
+/// rather than from storage of its own.
+///
+/// As the strings belong to the arena, they outlive the interner itself:
+/// `lookup` returns `&'bump str`, and every string remains valid for as long
+/// as the arena does (until it is reset), even after the `BumpLexicon` is
+/// dropped. This makes it possible to intern into, e.g., a per-compilation
+/// arena, and free everything at once by dropping it.
+#[derive(Debug)]
+pub struct BumpLexicon<'bump, K = Symbol, H = DefaultHashBuilder> {
+    bump: &'bump Bump,
+    map: HashTable<K>,
+    hasher: H,
+    vec: Vec<&'bump str>,
+}
+
+impl<'bump> BumpLexicon<'bump> {
+    pub fn new(bump: &'bump Bump) -> Self {
+        Self::with_hasher(bump, DefaultHashBuilder::default())
+    }
+}
+
+impl<'bump, K: Key, H: BuildHasher> BumpLexicon<'bump, K, H> {
+    pub fn with_hasher(bump: &'bump Bump, hasher: H) -> Self {
+        Self {
+            bump,
+            map: HashTable::new(),
+            hasher,
+            vec: Vec::new(),
+        }
+    }
+
+    /// Returns the arena strings are allocated from.
+    pub fn bump(&self) -> &'bump Bump {
+        self.bump
+    }
+
+    /// # Panics
+    /// Panics if `string` is new, but every key of type `K` is already taken.
+    pub fn intern(&mut self, string: &str) -> K {
+        match self.try_intern(string) {
+            Ok(id) => id,
+            Err(e) => panic!("{} while interning `{}`", e, string),
+        }
+    }
+
+    /// Like `intern`, but returns `LexiconError::KeysExhausted` rather than
+    /// panicking if `string` is new and every key of type `K` is taken.
+    pub fn try_intern(&mut self, string: &str) -> Result<K, LexiconError> {
+        let hash = self.hasher.hash_one(string);
+        if let Some(id) = self.find(hash, string) {
+            return Ok(id);
+        }
+
+        let id = K::try_from_usize(self.vec.len()).ok_or(LexiconError::KeysExhausted)?;
+        self.vec.push(self.bump.alloc_str(string));
+        let (vec, hasher) = (&self.vec, &self.hasher);
+        self.map
+            .insert_unique(hash, id, |id| hasher.hash_one(vec[id.to_usize()]));
+        Ok(id)
+    }
+
+    pub fn get(&self, string: &str) -> Option<K> {
+        self.find(self.hasher.hash_one(string), string)
+    }
+
+    fn find(&self, hash: u64, string: &str) -> Option<K> {
+        let vec = &self.vec;
+        self.map
+            .find(hash, |id| vec[id.to_usize()] == string)
+            .copied()
+    }
+
+    pub fn lookup(&self, id: K) -> &'bump str {
+        match self.try_lookup(id) {
+            Some(string) => string,
+            None => panic!("{}", LexiconError::InvalidKey(id.to_usize())),
+        }
+    }
+
+    pub fn try_lookup(&self, id: K) -> Option<&'bump str> {
+        self.vec.get(id.to_usize()).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bump_lexicon() {
+        let bump = Bump::new();
+        let (sym, string) = {
+            let mut lexicon = BumpLexicon::new(&bump);
+            let sym = lexicon.intern("bumped");
+            assert_eq!(lexicon.intern("bumped"), sym);
+            assert_eq!(lexicon.get("other"), None);
+            (sym, lexicon.lookup(sym))
+        };
+        // the string outlives the lexicon it was interned in
+        assert_eq!(string, "bumped");
+        assert_eq!(sym.as_u32(), 0);
+        assert!(bump.allocated_bytes() > 0);
+    }
+}
@@ -0,0 +1,154 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+use core::str::Utf8Error;
+
+use hashbrown::{DefaultHashBuilder, HashTable};
+
+use crate::arena::{ByteArena, Span};
+use crate::{ByteSymbol, Key, Lexicon, LexiconError};
+
+/// Interner for raw byte strings, e.g., for tokenizers working on bytes
+/// which may not (or not yet) be known to be valid UTF-8.
+///
+/// Byte strings are stored in the very same kind of arena as a `Lexicon`'s
+/// strings, and referenced by a `ByteSymbol` of their own. They are only
+/// ever validated as UTF-8 when resolved through `lookup_str`.
+#[derive(Clone, Debug)]
+pub struct ByteLexicon<K = ByteSymbol> {
+    map: HashTable<K>,
+    hasher: DefaultHashBuilder,
+    vec: Vec<Span>,
+    arena: ByteArena,
+}
+
+impl ByteLexicon {
+    pub fn new() -> Self {
+        Self::with_key()
+    }
+}
+
+impl<K: Key> ByteLexicon<K> {
+    /// Creates a new `ByteLexicon` handing out keys of type `K` rather than
+    /// `ByteSymbol`s.
+    pub fn with_key() -> Self {
+        Self {
+            map: HashTable::new(),
+            hasher: DefaultHashBuilder::default(),
+            vec: Vec::new(),
+            arena: ByteArena::with_capacity(Lexicon::BASE_CAPACITY),
+        }
+    }
+
+    pub fn intern(&mut self, bytes: &[u8]) -> K {
+        match self.try_intern(bytes) {
+            Ok(id) => id,
+            Err(e) => panic!("{} while interning `{}`", e, bytes.escape_ascii()),
+        }
+    }
+
+    /// Like `intern`, but returns an error rather than panicking if `bytes`
+    /// is new and cannot be interned (see `Lexicon::try_intern`).
+    pub fn try_intern(&mut self, bytes: &[u8]) -> Result<K, LexiconError> {
+        let hash = self.hasher.hash_one(bytes);
+        if let Some(id) = self.find(hash, bytes) {
+            return Ok(id);
+        }
+
+        let id = K::try_from_usize(self.vec.len()).ok_or(LexiconError::KeysExhausted)?;
+        if bytes.len() > ByteArena::MAX_LEN {
+            return Err(LexiconError::StringTooLong);
+        }
+        self.vec.push(self.arena.alloc(bytes));
+
+        let (vec, arena, hasher) = (&self.vec, &self.arena, &self.hasher);
+        self.map.insert_unique(hash, id, |id| {
+            hasher.hash_one(arena.get(vec[id.to_usize()]))
+        });
+        Ok(id)
+    }
+
+    pub fn get(&self, bytes: &[u8]) -> Option<K> {
+        self.find(self.hasher.hash_one(bytes), bytes)
+    }
+
+    fn find(&self, hash: u64, bytes: &[u8]) -> Option<K> {
+        let (vec, arena) = (&self.vec, &self.arena);
+        self.map
+            .find(hash, |id| arena.get(vec[id.to_usize()]) == bytes)
+            .copied()
+    }
+
+    pub fn lookup(&self, id: K) -> &[u8] {
+        match self.try_lookup(id) {
+            Some(bytes) => bytes,
+            None => panic!("symbol `{}` was not interned", id.to_usize()),
+        }
+    }
+
+    pub fn try_lookup(&self, id: K) -> Option<&[u8]> {
+        self.vec
+            .get(id.to_usize())
+            .map(|&span| self.arena.get(span))
+    }
+
+    /// Returns the bytes of `id` as a string, validating them as UTF-8 only
+    /// now.
+    ///
+    /// # Panics
+    /// Panics if `id` was not interned in this `ByteLexicon`.
+    pub fn lookup_str(&self, id: K) -> Result<&str, Utf8Error> {
+        core::str::from_utf8(self.lookup(id))
+    }
+
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+}
+
+impl Default for ByteLexicon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Key> core::ops::Index<K> for ByteLexicon<K> {
+    type Output = [u8];
+
+    fn index(&self, index: K) -> &Self::Output {
+        self.lookup(index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bytes() {
+        let mut lexicon = ByteLexicon::new();
+        let valid = lexicon.intern(b"token");
+        let invalid = lexicon.intern(&[0x66, 0xff, 0x6f]);
+        assert_eq!(lexicon.intern(b"token"), valid);
+        assert_eq!(lexicon.get(&[0x66, 0xff, 0x6f]), Some(invalid));
+        assert_eq!(&lexicon[invalid], &[0x66, 0xff, 0x6f]);
+        assert_eq!(lexicon.lookup_str(valid), Ok("token"));
+        assert!(lexicon.lookup_str(invalid).is_err());
+        assert_eq!(lexicon.len(), 2);
+    }
+
+    #[test]
+    fn test_long_bytes() {
+        let mut lexicon = ByteLexicon::new();
+        let long = alloc::vec![7; crate::arena::Arena::CHUNK_SIZE * 2];
+        let ids = (0..100u8).map(|b| lexicon.intern(&[b])).collect::<Vec<_>>();
+        let id = lexicon.intern(&long);
+        assert_eq!(lexicon.lookup(id), &long[..]);
+        for (b, id) in ids.into_iter().enumerate() {
+            assert_eq!(lexicon.lookup(id), &[b as u8]);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{Lexicon, Symbol, SymbolRemap};
+
+impl Lexicon {
+    /// Renumbers the strings of this `Lexicon` in lexicographic order,
+    /// returning the resulting `Lexicon` along with the remapping from the
+    /// current symbols to the new ones.
+    ///
+    /// Symbols handed out while interning from several threads (e.g., by a
+    /// `ConcurrentLexicon`) depend on how the threads happened to interleave;
+    /// renumbering afterwards makes them independent of it, keeping build
+    /// outputs deterministic.
+    pub fn canonicalize_sorted(&self) -> (Lexicon, SymbolRemap) {
+        self.canonicalize_by_occurrence(None)
+    }
+
+    /// Renumbers the strings of this `Lexicon` in the order their symbols
+    /// first occur in `order` (e.g., the symbols of every input, in input
+    /// order), followed by any other string in lexicographic order. Returns
+    /// the resulting `Lexicon` along with the remapping from the current
+    /// symbols to the new ones. See `canonicalize_sorted`.
+    ///
+    /// # Panics
+    /// Panics if `order` holds a symbol not interned in this `Lexicon`.
+    pub fn canonicalize_by_occurrence(
+        &self,
+        order: impl IntoIterator<Item = Symbol>,
+    ) -> (Lexicon, SymbolRemap) {
+        let mut lexicon = Lexicon::with_capacity(Lexicon::BASE_CAPACITY);
+        let mut map = alloc::vec![None; self.symbol_count()];
+        let sorted = self.sorted_index();
+        let remaining = sorted.as_slice().iter().copied();
+        for id in order.into_iter().chain(remaining) {
+            let string = self.lookup(id);
+            let new = &mut map[id.as_usize()];
+            if new.is_none() {
+                *new = Some(lexicon.intern(string));
+            }
+        }
+        (lexicon, SymbolRemap::from_map(map))
+    }
+
+    /// Renumbers the strings of this `Lexicon` by how often they were
+    /// interned (see `track_frequencies`), giving the smallest symbols to
+    /// the most frequent strings, followed by equally frequent ones in
+    /// lexicographic order. Returns the resulting `Lexicon`, which carries
+    /// the counts over, along with the remapping from the current symbols
+    /// to the new ones.
+    ///
+    /// Meant as a final pass once interning is done, such that the hottest
+    /// symbols index the front of dense tables (e.g., a `SymbolMap`),
+    /// sharing cache lines. Without counts, this is the same as
+    /// `canonicalize_sorted`.
+    pub fn canonicalize_by_frequency(&self) -> (Lexicon, SymbolRemap) {
+        let mut top = self.top_n(self.len());
+        let sorted = self.sorted_index();
+        let rank = |id| sorted.rank(id).unwrap_or(usize::MAX);
+        top.sort_by_key(|&(id, count)| (core::cmp::Reverse(count), rank(id)));
+
+        let (mut lexicon, remap) = self.canonicalize_by_occurrence(top.iter().map(|&(id, _)| id));
+        if self.tracks_frequencies() {
+            lexicon.track_frequencies();
+            for &(id, count) in &top {
+                lexicon.frequencies.set(remap[id].as_usize(), count);
+            }
+        }
+        (lexicon, remap)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_canonicalize() {
+        let a = ["b", "c", "a"].iter().collect::<Lexicon>();
+        let b = ["c", "a", "b"].iter().collect::<Lexicon>();
+        let (sorted_a, remap) = a.canonicalize_sorted();
+        let (sorted_b, _) = b.canonicalize_sorted();
+        assert_eq!(
+            sorted_a.iter().collect::<Vec<_>>(),
+            sorted_b.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(sorted_a.lookup(remap[Symbol::new(0)]), "b");
+        assert_eq!(remap[Symbol::new(2)], Symbol::new(0));
+
+        let c = b.get("c").unwrap();
+        let (by_use, remap) = b.canonicalize_by_occurrence([c, c]);
+        assert_eq!(
+            by_use.join(&[Symbol::new(0), Symbol::new(1), Symbol::new(2)], ""),
+            "cab"
+        );
+        assert_eq!(remap[c], Symbol::new(0));
+    }
+
+    #[test]
+    fn test_canonicalize_by_frequency() {
+        let mut lexicon = Lexicon::default();
+        lexicon.track_frequencies();
+        for string in ["rare", "hot", "warm", "hot", "b", "warm", "hot"] {
+            lexicon.intern(string);
+        }
+        let (by_frequency, remap) = lexicon.canonicalize_by_frequency();
+        let strings = by_frequency.iter().map(|(_, s)| s).collect::<Vec<_>>();
+        assert_eq!(strings, ["hot", "warm", "b", "rare"]);
+        assert_eq!(remap[lexicon.get("rare").unwrap()], Symbol::new(3));
+        assert_eq!(by_frequency.frequency(Symbol::new(1)), Some(2));
+
+        let (sorted, _) = Lexicon::default().canonicalize_by_frequency();
+        assert!(sorted.is_empty());
+    }
+}
@@ -0,0 +1,152 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use crate::arena::{Arena, Span};
+use crate::{Backend, Interner, Lexicon, LexiconError, Resolver, Symbol, Symbolic};
+
+/// Case-insensitive interner, e.g., for the identifiers of SQL or Pascal.
+///
+/// Strings are keyed on their lowercase form (as by `str::to_lowercase`), so
+/// `"Foo"` and `"FOO"` are interned as the same `Symbol`. Each symbol still
+/// resolves to the spelling its string was first interned with, while its
+/// lowercase form remains available through `CaselessLexicon::folded`.
+#[derive(Clone, Debug)]
+pub struct CaselessLexicon {
+    /// Lowercase form of every interned string.
+    folded: Lexicon,
+    /// First-seen spelling of every interned string, indexed by symbol.
+    spellings: Vec<Span>,
+    arena: Arena,
+}
+
+/// Returns the lowercase form of `string`, without allocating if it is
+/// already lowercase ASCII.
+pub(crate) fn fold(string: &str) -> Cow<'_, str> {
+    if string
+        .bytes()
+        .all(|b| b.is_ascii() && !b.is_ascii_uppercase())
+    {
+        Cow::Borrowed(string)
+    } else {
+        Cow::Owned(string.to_lowercase())
+    }
+}
+
+impl CaselessLexicon {
+    pub fn new() -> Self {
+        Self {
+            folded: Lexicon::default(),
+            spellings: Vec::new(),
+            arena: Arena::with_capacity(Lexicon::BASE_CAPACITY),
+        }
+    }
+
+    pub fn intern(&mut self, string: &str) -> Symbol {
+        match self.try_intern(string) {
+            Ok(id) => id,
+            Err(e) => panic!("{} while interning `{}`", e, string),
+        }
+    }
+
+    /// Like `intern`, but returns an error rather than panicking if `string`
+    /// is new and cannot be interned (see `Lexicon::try_intern`).
+    pub fn try_intern(&mut self, string: &str) -> Result<Symbol, LexiconError> {
+        let id = self.folded.try_intern(&fold(string))?;
+        if id.as_usize() == self.spellings.len() {
+            self.spellings.push(self.arena.alloc(string));
+        }
+        Ok(id)
+    }
+
+    /// Returns the `Symbol` of `string` (in any case) if it has already been
+    /// interned.
+    pub fn get(&self, string: &str) -> Option<Symbol> {
+        self.folded.get(&fold(string))
+    }
+
+    /// Returns the spelling `id` was first interned with.
+    pub fn lookup(&self, id: Symbol) -> &str {
+        match self.try_lookup(id) {
+            Some(string) => string,
+            None => panic!("symbol `{}` was not interned", id.as_u32()),
+        }
+    }
+
+    pub fn try_lookup(&self, id: Symbol) -> Option<&str> {
+        Some(self.arena.get(*self.spellings.get(id.as_usize())?))
+    }
+
+    /// Returns the lowercase form of the string of `id`, i.e., the form its
+    /// string is keyed on.
+    pub fn folded(&self, id: Symbol) -> &str {
+        self.folded.lookup(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.spellings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spellings.is_empty()
+    }
+}
+
+impl Default for CaselessLexicon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Symbolic> core::ops::Index<S> for CaselessLexicon {
+    type Output = str;
+
+    fn index(&self, index: S) -> &Self::Output {
+        self.lookup(index.get_symbol())
+    }
+}
+
+impl Resolver for CaselessLexicon {
+    fn resolve(&self, id: Symbol) -> &str {
+        self.lookup(id)
+    }
+
+    fn try_resolve(&self, id: Symbol) -> Option<&str> {
+        self.try_lookup(id)
+    }
+}
+
+impl Interner for CaselessLexicon {
+    type Error = LexiconError;
+
+    fn try_intern(&mut self, string: &str) -> Result<Symbol, LexiconError> {
+        self.try_intern(string)
+    }
+
+    fn get(&self, string: &str) -> Option<Symbol> {
+        self.get(string)
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_caseless() {
+        let mut lexicon = CaselessLexicon::new();
+        let select = lexicon.intern("Select");
+        assert_eq!(lexicon.intern("SELECT"), select);
+        assert_eq!(lexicon.get("select"), Some(select));
+        assert_eq!(lexicon.lookup(select), "Select");
+        assert_eq!(lexicon.folded(select), "select");
+
+        let strasse = lexicon.intern("STRASSE");
+        assert_ne!(lexicon.intern("Straße"), strasse);
+        assert_eq!(lexicon.intern("ÉTÉ"), lexicon.intern("été"));
+        assert_eq!(lexicon.len(), 4);
+    }
+}
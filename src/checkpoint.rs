@@ -0,0 +1,211 @@
+use crate::arena::Mark;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use crate::{Backend, Key, Lexicon, LexiconError};
+
+/// Snapshot of the size of a `Lexicon`, which it can later be truncated back
+/// to via `Lexicon::truncate_to`. Created by `Lexicon::checkpoint`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Checkpoint<M = Mark> {
+    len: usize,
+    mark: M,
+}
+
+impl<M> Checkpoint<M> {
+    /// Returns the number of strings the `Lexicon` held when this checkpoint
+    /// was taken. Every `Symbol` below this count survives truncation.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<K: Key, H: BuildHasher, B: Backend> Lexicon<K, H, B> {
+    /// Records the current state of this `Lexicon`, such that every string
+    /// interned after this call can later be discarded with `truncate_to`
+    /// (e.g., when backtracking out of a speculative parse).
+    pub fn checkpoint(&self) -> Checkpoint<B::Mark> {
+        Checkpoint {
+            len: self.vec.len(),
+            mark: self.backend.mark(),
+        }
+    }
+
+    /// Removes every string interned since `checkpoint` was taken,
+    /// invalidating their `Symbol`s. `Symbol`s interned before it remain
+    /// valid.
+    ///
+    /// # Panics
+    /// Panics if `checkpoint` is stale, i.e., if this `Lexicon` was already
+    /// truncated (or cleared) past `checkpoint` since it was taken. See
+    /// `try_truncate_to` for a non-panicking alternative.
+    pub fn truncate_to(&mut self, checkpoint: Checkpoint<B::Mark>) {
+        if let Err(e) = self.try_truncate_to(checkpoint) {
+            panic!("{}", e)
+        }
+    }
+
+    /// Like `truncate_to`, but returns `LexiconError::StaleCheckpoint`
+    /// (leaving this `Lexicon` untouched) rather than panicking if
+    /// `checkpoint` is stale.
+    pub fn try_truncate_to(&mut self, checkpoint: Checkpoint<B::Mark>) -> Result<(), LexiconError> {
+        // reserved keys may have been defined after `checkpoint` was taken,
+        // so only the latest of the other keys is checked
+        let fresh = checkpoint.len <= self.vec.len()
+            && (0..checkpoint.len)
+                .rev()
+                .find(|idx| !self.defined.contains(idx))
+                .is_none_or(|idx| self.backend.precedes(self.vec[idx], checkpoint.mark));
+        if !fresh {
+            return Err(LexiconError::StaleCheckpoint);
+        }
+        // scopes opened after `checkpoint` can no longer be popped back to
+        let open = self.scopes.partition_point(|s| s.len <= checkpoint.len);
+        self.scopes.truncate(open);
+        self.pending.retain(|&idx| idx < checkpoint.len);
+        self.keywords = self.keywords.min(checkpoint.len);
+        let (map, vec, backend, hasher, stored) = (
+            &mut self.map,
+            &mut self.vec,
+            &self.backend,
+            &self.hasher,
+            &mut self.stored,
+        );
+        let mut unmap = |idx: usize, span| {
+            let string = backend.get(span);
+            *stored -= string.len();
+            let hash = hasher.hash_one(string);
+            if let Ok(entry) = map.find_entry(hash, |id| id.to_usize() == idx) {
+                entry.remove();
+            }
+        };
+        for (idx, span) in vec.drain(checkpoint.len..).enumerate() {
+            unmap(checkpoint.len + idx, span);
+        }
+        self.side_tables.truncate(checkpoint.len);
+        self.frequencies.truncate(checkpoint.len);
+        self.sorted.invalidate();
+        // reserved keys defined since are reserved again
+        let mut undefined = Vec::new();
+        self.defined.retain(|&idx| {
+            let late = idx < checkpoint.len && !backend.precedes(vec[idx], checkpoint.mark);
+            if late {
+                unmap(idx, vec[idx]);
+                undefined.push(idx);
+            }
+            idx < checkpoint.len && !late
+        });
+        self.truncate_aliases(checkpoint.len, checkpoint.mark);
+        // every remaining string precedes `mark`, while the strings
+        // allocated since were just removed from both tables
+        self.backend.truncate(checkpoint.mark);
+        for idx in undefined {
+            self.vec[idx] = self.backend.alloc("");
+            self.pending.insert(idx);
+        }
+        if let Some(trie) = &mut self.prefixes {
+            let pending = &self.pending;
+            trie.retain(|id| id.to_usize() < checkpoint.len && !pending.contains(&id.to_usize()));
+        }
+        Ok(())
+    }
+
+    /// Opens a new scope: every string interned from now on (and not already
+    /// present) is discarded once the scope is closed with `pop_scope`,
+    /// while strings interned in enclosing scopes survive. Useful for, e.g.,
+    /// macro expansion or evaluating REPL cells.
+    pub fn push_scope(&mut self) {
+        let checkpoint = self.checkpoint();
+        self.scopes.push(checkpoint);
+    }
+
+    /// Closes the innermost scope opened with `push_scope`, discarding every
+    /// string interned within it and invalidating their `Symbol`s. Returns
+    /// `false` if there was no scope to close.
+    pub fn pop_scope(&mut self) -> bool {
+        match self.scopes.pop() {
+            Some(checkpoint) => {
+                self.truncate_to(checkpoint);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the number of currently open scopes.
+    pub fn scope_depth(&self) -> usize {
+        self.scopes.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_truncate_to() {
+        let mut lexicon = Lexicon::with_capacity(2);
+        let kept = lexicon.intern("kept");
+        let checkpoint = lexicon.checkpoint();
+        for i in 0..50 {
+            lexicon.intern(&i.to_string());
+        }
+        assert_eq!(lexicon.intern("kept"), kept);
+
+        lexicon.truncate_to(checkpoint);
+        lexicon.validate();
+        assert_eq!(lexicon.len(), 1);
+        assert_eq!(lexicon.lookup(kept), "kept");
+        assert_eq!(lexicon.get("0"), None);
+        assert_eq!(lexicon.intern("new").as_usize(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_stale_checkpoint() {
+        let mut lexicon = Lexicon::default();
+        lexicon.intern("a");
+        let checkpoint = lexicon.checkpoint();
+        lexicon.clear();
+        lexicon.truncate_to(checkpoint);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_checkpoint_truncated_past() {
+        let mut lexicon = Lexicon::default();
+        let start = lexicon.checkpoint();
+        lexicon.intern("a");
+        let later = lexicon.checkpoint();
+        lexicon.truncate_to(start);
+        lexicon.intern("a much longer string");
+        lexicon.intern("b");
+        lexicon.truncate_to(later);
+    }
+
+    #[test]
+    fn test_scopes() {
+        let mut lexicon = Lexicon::default();
+        let outer = lexicon.intern("outer");
+
+        lexicon.push_scope();
+        let middle = lexicon.intern("middle");
+        lexicon.push_scope();
+        assert_eq!(lexicon.intern("outer"), outer);
+        lexicon.intern("inner");
+        assert_eq!(lexicon.scope_depth(), 2);
+
+        assert!(lexicon.pop_scope());
+        assert_eq!(lexicon.get("inner"), None);
+        assert_eq!(lexicon.lookup(middle), "middle");
+        assert!(lexicon.pop_scope());
+        assert_eq!(lexicon.get("middle"), None);
+        assert_eq!(lexicon.lookup(outer), "outer");
+        assert!(!lexicon.pop_scope());
+        lexicon.validate();
+    }
+}
@@ -0,0 +1,285 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::thread;
+
+use crate::arena::Arena;
+use crate::slots::Slots;
+use crate::Backend;
+use crate::{Interner, Lexicon, LexiconError, Resolver, Symbol};
+
+/// String interner that may be shared (e.g., behind an `Arc`) and interned
+/// into from multiple threads at once.
+///
+/// Rather than funneling every call through a single lock, the string-to-
+/// `Symbol` table is split into independently locked *shards*, with a string
+/// always being routed to the same shard based on its hash. Threads
+/// interning different strings will thus rarely contend with one another.
+///
+/// Interning a string that is already present only requires a shared read
+/// lock on its shard, while resolving a `Symbol` never locks at all: interned
+/// strings are published into an append-only table of atomic slots, so the
+/// whole interner works through `&self` and may simply be stored in an `Arc`.
+///
+/// `Symbol`s are still handed out sequentially from a single atomic counter,
+/// so a `ConcurrentLexicon` can be converted into a regular `Lexicon` (via
+/// `ConcurrentLexicon::into_lexicon`) without invalidating any of them.
+pub struct ConcurrentLexicon {
+    shards: Box<[RwLock<Shard>]>,
+    slots: Slots,
+    next: AtomicU32,
+    hasher: RandomState,
+}
+
+/// Each shard owns the storage for the strings routed to it, so allocating a
+/// new string only ever requires holding the lock of its shard.
+pub(crate) struct Shard {
+    pub map: HashMap<&'static str, Symbol>,
+    pub arena: Arena,
+}
+
+impl Shard {
+    pub fn new() -> RwLock<Self> {
+        RwLock::new(Shard {
+            map: HashMap::default(),
+            arena: Arena::with_capacity(Lexicon::BASE_CAPACITY),
+        })
+    }
+
+    pub fn read<'a>(shard: &'a RwLock<Self>, string: &str) -> RwLockReadGuard<'a, Self> {
+        match shard.read() {
+            Ok(guard) => guard,
+            Err(e) => {
+                eprintln!("{}", e);
+                panic!("poisoned while interning `{}`", string)
+            }
+        }
+    }
+
+    pub fn write<'a>(shard: &'a RwLock<Self>, string: &str) -> RwLockWriteGuard<'a, Self> {
+        match shard.write() {
+            Ok(guard) => guard,
+            Err(e) => {
+                eprintln!("{}", e);
+                panic!("poisoned while interning `{}`", string)
+            }
+        }
+    }
+}
+
+/// Default number of shards, scaled to the available parallelism.
+pub(crate) fn default_shards() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get()) * 4
+}
+
+impl ConcurrentLexicon {
+    /// Creates a new `ConcurrentLexicon` with a number of shards scaled to
+    /// the available parallelism of the current machine.
+    pub fn new() -> Self {
+        Self::with_shards(default_shards())
+    }
+
+    /// Creates a new `ConcurrentLexicon` with (at least) `shards` shards. The
+    /// number of shards is always rounded up to a power of two.
+    pub fn with_shards(shards: usize) -> Self {
+        let shards = shards.max(1).next_power_of_two();
+        Self {
+            shards: (0..shards).map(|_| Shard::new()).collect(),
+            slots: Slots::new(),
+            next: AtomicU32::new(0),
+            hasher: RandomState::new(),
+        }
+    }
+
+    pub fn intern(&self, string: &str) -> Symbol {
+        match self.try_intern(string) {
+            Ok(id) => id,
+            Err(e) => panic!("{} while interning `{}`", e, string),
+        }
+    }
+
+    /// Like `intern`, but returns `LexiconError::KeysExhausted` rather than
+    /// panicking if `string` is new and every `Symbol` has been handed out.
+    pub fn try_intern(&self, string: &str) -> Result<Symbol, LexiconError> {
+        let shard = &self.shards[self.shard_index(string)];
+        if let Some(&id) = Shard::read(shard, string).map.get(string) {
+            return Ok(id);
+        }
+
+        let mut shard = Shard::write(shard, string);
+        // another thread may have interned it while we waited for the lock
+        if let Some(&id) = shard.map.get(string) {
+            return Ok(id);
+        }
+
+        // the counter must not wrap around, or symbols would be handed out
+        // a second time
+        let id = match self
+            .next
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_add(1))
+        {
+            Ok(n) => Symbol::new(n),
+            Err(_) => return Err(LexiconError::KeysExhausted),
+        };
+        // the shard's arena is only dropped along with `self`, and `slots`
+        // never hands out references outliving `&self`
+        let string = unsafe { shard.arena.alloc_stable(string) };
+        // the slot must be published before the symbol becomes reachable
+        // through the map, as readers may resolve it without locking
+        self.slots.publish(id.as_usize(), string);
+        shard.map.insert(string, id);
+        Ok(id)
+    }
+
+    pub fn lookup(&self, id: Symbol) -> &str {
+        match self.try_lookup(id) {
+            Some(string) => string,
+            None => panic!("symbol `{}` was not interned", id.as_u32()),
+        }
+    }
+
+    /// Returns the string corresponding to `id`, or `None` if `id` was not
+    /// (or not yet) interned in this `ConcurrentLexicon`.
+    pub fn try_lookup(&self, id: Symbol) -> Option<&str> {
+        self.slots.get(id.as_usize())
+    }
+
+    /// Returns the number of strings interned so far. Since other threads
+    /// may be interning concurrently, this should only be treated as a
+    /// snapshot.
+    pub fn len(&self) -> usize {
+        self.next.load(Ordering::Relaxed) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Converts this into a regular (single-threaded) `Lexicon`. Every
+    /// `Symbol` returned by this interner remains valid for the resulting
+    /// `Lexicon`.
+    pub fn into_lexicon(self) -> Lexicon {
+        let mut lexicon = Lexicon::default();
+        for idx in 0..self.len() {
+            // with `self` owned, every reserved slot has been published
+            lexicon.intern(self.slots.get(idx).unwrap());
+        }
+        lexicon
+    }
+
+    fn shard_index(&self, string: &str) -> usize {
+        self.hasher.hash_one(string) as usize & (self.shards.len() - 1)
+    }
+}
+
+impl Default for ConcurrentLexicon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Resolver for ConcurrentLexicon {
+    fn resolve(&self, id: Symbol) -> &str {
+        self.lookup(id)
+    }
+
+    fn try_resolve(&self, id: Symbol) -> Option<&str> {
+        self.try_lookup(id)
+    }
+}
+
+/// Interning only requires `&self`, so the same `ConcurrentLexicon` may also
+/// be shared between threads while being used through this trait elsewhere.
+impl Interner for ConcurrentLexicon {
+    type Error = LexiconError;
+
+    fn try_intern(&mut self, string: &str) -> Result<Symbol, LexiconError> {
+        ConcurrentLexicon::try_intern(self, string)
+    }
+
+    fn get(&self, string: &str) -> Option<Symbol> {
+        let shard = &self.shards[self.shard_index(string)];
+        Shard::read(shard, string).map.get(string).copied()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl std::fmt::Debug for ConcurrentLexicon {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConcurrentLexicon")
+            .field("shards", &self.shards.len())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_concurrent_intern() {
+        let lexicon = Arc::new(ConcurrentLexicon::with_shards(4));
+        let words = ["fn", "let", "match", "if", "else", "while"];
+
+        let handles = (0..8)
+            .map(|_| {
+                let lexicon = Arc::clone(&lexicon);
+                thread::spawn(move || words.map(|w| lexicon.intern(w)))
+            })
+            .collect::<Vec<_>>();
+        let results = handles
+            .into_iter()
+            .map(|h| h.join().unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(results.windows(2).all(|w| w[0] == w[1]));
+        for (word, sym) in words.iter().zip(results[0]) {
+            assert_eq!(lexicon.lookup(sym), *word);
+        }
+        assert_eq!(lexicon.len(), words.len());
+        assert_eq!(lexicon.try_lookup(Symbol::new(words.len() as u32)), None);
+    }
+
+    #[test]
+    fn test_lookup_while_interning() {
+        let lexicon = Arc::new(ConcurrentLexicon::new());
+        let writer = {
+            let lexicon = Arc::clone(&lexicon);
+            thread::spawn(move || {
+                (0..1000)
+                    .map(|i| lexicon.intern(&i.to_string()))
+                    .collect::<Vec<_>>()
+            })
+        };
+        let syms = writer.join().unwrap();
+        let readers = (0..4)
+            .map(|_| {
+                let lexicon = Arc::clone(&lexicon);
+                let syms = syms.clone();
+                thread::spawn(move || {
+                    syms.iter()
+                        .enumerate()
+                        .all(|(i, &sym)| lexicon.lookup(sym) == i.to_string())
+                })
+            })
+            .collect::<Vec<_>>();
+        assert!(readers.into_iter().all(|h| h.join().unwrap()));
+    }
+
+    #[test]
+    fn test_into_lexicon() {
+        let concurrent = ConcurrentLexicon::with_shards(2);
+        let syms = ["a", "b", "c"].map(|s| concurrent.intern(s));
+        let lexicon = concurrent.into_lexicon();
+        for (sym, s) in syms.iter().zip(["a", "b", "c"]) {
+            assert_eq!(lexicon.lookup(*sym), s);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use alloc::vec::Vec;
+
+use crate::{Lexicon, Symbol};
+
+/// Differences between two lexicons, as computed by `Lexicon::diff`. The
+/// lexicon `diff` was called on is referred to as the *old* one, and its
+/// argument as the *new* one.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LexiconDiff<'a> {
+    /// Strings (along with their old `Symbol`) missing from the new lexicon.
+    pub removed: Vec<(Symbol, &'a str)>,
+    /// Strings (along with their new `Symbol`) missing from the old lexicon.
+    pub added: Vec<(Symbol, &'a str)>,
+    /// Strings present in both lexicons, but under different symbols, given
+    /// as `(string, old, new)`.
+    pub moved: Vec<(&'a str, Symbol, Symbol)>,
+}
+
+impl LexiconDiff<'_> {
+    /// Whether both lexicons hold the same strings under the same symbols.
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.added.is_empty() && self.moved.is_empty()
+    }
+
+    /// Whether every `Symbol` of the old lexicon resolves to the same string
+    /// in the new one, i.e., whether data referring to the old lexicon's
+    /// symbols (such as a cache persisted alongside it) may be used with the
+    /// new lexicon as is. The new lexicon may hold additional strings.
+    pub fn is_compatible(&self) -> bool {
+        self.removed.is_empty() && self.moved.is_empty()
+    }
+}
+
+impl Lexicon {
+    /// Computes the differences between this (old) `Lexicon` and `other`
+    /// (the new one). Strings are listed in the order they were interned.
+    pub fn diff<'a>(&'a self, other: &'a Lexicon) -> LexiconDiff<'a> {
+        let mut diff = LexiconDiff::default();
+        for (old, string) in self {
+            match other.get(string) {
+                None => diff.removed.push((old, string)),
+                Some(new) if new != old => diff.moved.push((string, old, new)),
+                Some(_) => {}
+            }
+        }
+        diff.added = other
+            .iter()
+            .filter(|(_, string)| self.get(string).is_none())
+            .collect();
+        diff
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let old = ["a", "b", "c"].iter().collect::<Lexicon>();
+        let new = ["a", "c", "d"].iter().collect::<Lexicon>();
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.removed, [(Symbol::new(1), "b")]);
+        assert_eq!(diff.added, [(Symbol::new(2), "d")]);
+        assert_eq!(diff.moved, [("c", Symbol::new(2), Symbol::new(1))]);
+        assert!(!diff.is_compatible());
+    }
+
+    #[test]
+    fn test_compatible() {
+        let old = ["a", "b"].iter().collect::<Lexicon>();
+        let new = ["a", "b", "c"].iter().collect::<Lexicon>();
+        assert!(old.diff(&new).is_compatible());
+        assert!(!new.diff(&old).is_compatible());
+        assert!(old.diff(&old).is_empty());
+    }
+}
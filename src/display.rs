@@ -0,0 +1,103 @@
+use alloc::string::String;
+use core::fmt;
+use core::hash::BuildHasher;
+
+use crate::{Backend, Key, Lexicon};
+
+/// Symbols of a `Lexicon` displayed as their strings, separated by `sep`.
+struct Joined<'a, K: Key, H, B: Backend> {
+    lexicon: &'a Lexicon<K, H, B>,
+    ids: &'a [K],
+    sep: &'a str,
+}
+
+impl<K: Key, H: BuildHasher, B: Backend> fmt::Display for Joined<'_, K, H, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, &id) in self.ids.iter().enumerate() {
+            if i > 0 {
+                f.write_str(self.sep)?;
+            }
+            f.write_str(self.lexicon.lookup(id))?;
+        }
+        Ok(())
+    }
+}
+
+impl<K: Key, H: BuildHasher, B: Backend> Lexicon<K, H, B> {
+    /// Returns the string of `id` as a `Display`able value, e.g., for error
+    /// messages: `format!("unknown name `{}`", lexicon.display(sym))`.
+    ///
+    /// # Panics
+    /// Panics if `id` was not interned in this `Lexicon`.
+    pub fn display(&self, id: K) -> impl fmt::Display + '_ {
+        self.lookup(id)
+    }
+
+    /// Like `display`, but for several symbols at once, which are displayed
+    /// as a comma-separated list of their strings.
+    ///
+    /// # Panics
+    /// Panics when formatted if any of `ids` was not interned in this
+    /// `Lexicon`.
+    pub fn display_all<'a>(&'a self, ids: &'a [K]) -> impl fmt::Display + 'a {
+        self.joined(ids, ", ")
+    }
+
+    /// Joins the strings of `ids` with `sep` into a new `String`, e.g., to
+    /// build the qualified path `a::b::c` out of its segments.
+    ///
+    /// # Panics
+    /// Panics if any of `ids` was not interned in this `Lexicon`.
+    pub fn join(&self, ids: &[K], sep: &str) -> String {
+        let strings = ids.iter().map(|&id| self.lookup(id));
+        let len = strings.map(str::len).sum::<usize>() + sep.len() * ids.len().saturating_sub(1);
+        let mut joined = String::with_capacity(len);
+        for (i, &id) in ids.iter().enumerate() {
+            if i > 0 {
+                joined.push_str(sep);
+            }
+            joined.push_str(self.lookup(id));
+        }
+        joined
+    }
+
+    /// Like `join`, but returns the joined strings as a `Display`able value
+    /// rather than allocating a `String`.
+    ///
+    /// # Panics
+    /// Panics when formatted if any of `ids` was not interned in this
+    /// `Lexicon`.
+    pub fn joined<'a>(&'a self, ids: &'a [K], sep: &'a str) -> impl fmt::Display + 'a {
+        Joined {
+            lexicon: self,
+            ids,
+            sep,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn test_display() {
+        let mut lexicon = Lexicon::default();
+        let syms = ["a", "b", "c"].map(|s| lexicon.intern(s));
+        let message = format!("unknown name `{}`", lexicon.display(syms[1]));
+        assert_eq!(message, "unknown name `b`");
+        assert_eq!(format!("[{}]", lexicon.display_all(&syms)), "[a, b, c]");
+        assert_eq!(format!("{}", lexicon.display_all(&[])), "");
+    }
+
+    #[test]
+    fn test_join() {
+        let mut lexicon = Lexicon::default();
+        let path = ["std", "vec", "Vec"].map(|s| lexicon.intern(s));
+        let joined = lexicon.join(&path, "::");
+        assert_eq!(joined, "std::vec::Vec");
+        assert_eq!(format!("{}", lexicon.joined(&path[1..], "/")), "vec/Vec");
+        assert_eq!(lexicon.join(&[], "::"), "");
+    }
+}
@@ -0,0 +1,78 @@
+use core::fmt;
+
+/// Error returned by the fallible operations of a `Lexicon`. Each of these
+/// has a panicking counterpart (e.g., `try_intern` and `intern`), which
+/// panics with this error's message instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LexiconError {
+    /// Every key of the `Lexicon`'s key type has already been handed out, so
+    /// no further strings may be interned.
+    KeysExhausted,
+    /// A string was longer than the `u32::MAX` bytes a `Lexicon` may store.
+    StringTooLong,
+    /// A key (given by its index) was not interned in the `Lexicon` it was
+    /// resolved against.
+    InvalidKey(usize),
+    /// A `Checkpoint` was used after its `Lexicon` had already been truncated
+    /// (or cleared) past it.
+    StaleCheckpoint,
+    /// A key (given by its index) was defined without having been reserved
+    /// through `Lexicon::reserve_symbol`, or was already defined.
+    NotReserved(usize),
+    /// A reserved key was defined as a string already interned under
+    /// another key (given by its index).
+    AlreadyInterned(usize),
+    /// A string interned into an `AsciiLexicon` was not ASCII.
+    NonAscii,
+    /// Storing a string would have exceeded the memory limit set through
+    /// `Lexicon::set_memory_limit`.
+    MemoryLimitExceeded,
+}
+
+impl fmt::Display for LexiconError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LexiconError::KeysExhausted => write!(f, "lexicon keys exhausted"),
+            LexiconError::StringTooLong => write!(f, "string is too long to intern"),
+            LexiconError::InvalidKey(idx) => write!(f, "symbol `{}` was not interned", idx),
+            LexiconError::StaleCheckpoint => write!(f, "checkpoint is stale for this lexicon"),
+            LexiconError::NotReserved(idx) => write!(f, "symbol `{}` is not reserved", idx),
+            LexiconError::AlreadyInterned(idx) => {
+                write!(f, "string is already interned as symbol `{}`", idx)
+            }
+            LexiconError::NonAscii => write!(f, "string is not ASCII"),
+            LexiconError::MemoryLimitExceeded => write!(f, "lexicon memory limit exceeded"),
+        }
+    }
+}
+
+impl core::error::Error for LexiconError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Lexicon;
+
+    #[test]
+    fn test_fallible_api() {
+        let mut lexicon = Lexicon::default();
+        let sym = lexicon.intern("a");
+        let checkpoint = lexicon.checkpoint();
+        assert_eq!(lexicon.lookup_checked(sym), Ok("a"));
+
+        lexicon.clear();
+        assert_eq!(
+            lexicon.lookup_checked(sym),
+            Err(LexiconError::InvalidKey(0))
+        );
+        assert_eq!(
+            lexicon.try_truncate_to(checkpoint),
+            Err(LexiconError::StaleCheckpoint)
+        );
+        assert_eq!(
+            LexiconError::InvalidKey(3).to_string(),
+            "symbol `3` was not interned"
+        );
+    }
+}
@@ -0,0 +1,368 @@
+//! Textual exports of a `Lexicon`, for tooling outside of Rust (e.g.,
+//! analysis scripts) to consume the interned strings along with their ids.
+//!
+//! - JSON: an array of the strings, in order of symbol, so that the string
+//!   of `Symbol` `i` is at index `i` (the same shape as the `serde` encoding).
+//! - CSV: an `id,string` header, followed by one record per string, in order
+//!   of symbol, quoted as in RFC 4180 where needed.
+//!
+//! Symbols reserved but not defined yet (see `Lexicon::reserve_symbol`) are
+//! exported as `null` in JSON, and as an empty (unquoted) field in CSV, where
+//! the empty string is quoted as `""` instead. Both are reserved again when
+//! imported.
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use crate::binary::invalid_data;
+use crate::Lexicon;
+
+impl Lexicon {
+    /// Returns the JSON export of this `Lexicon`, one string per line.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("[");
+        for (i, string) in self.slots().enumerate() {
+            json.push_str(if i == 0 { "\n  " } else { ",\n  " });
+            let string = match string {
+                Some(string) => string,
+                None => {
+                    json.push_str("null");
+                    continue;
+                }
+            };
+            json.push('"');
+            for c in string.chars() {
+                match c {
+                    '"' => json.push_str("\\\""),
+                    '\\' => json.push_str("\\\\"),
+                    '\n' => json.push_str("\\n"),
+                    '\r' => json.push_str("\\r"),
+                    '\t' => json.push_str("\\t"),
+                    c if c < ' ' => write!(json, "\\u{:04x}", c as u32).unwrap(),
+                    c => json.push(c),
+                }
+            }
+            json.push('"');
+        }
+        json.push_str(if self.symbol_count() == 0 { "]" } else { "\n]" });
+        json
+    }
+
+    pub fn write_json(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(self.to_json().as_bytes())
+    }
+
+    /// Reads a `Lexicon` from its JSON export (or any JSON array of unique
+    /// strings and `null`s), interning the string at index `i` as `Symbol`
+    /// `i`, or reserving it for a `null`.
+    pub fn from_json(json: &str) -> io::Result<Self> {
+        let mut parser = JsonParser { json, pos: 0 };
+        let mut lexicon = Lexicon::default();
+        parser.expect(b'[')?;
+        if !parser.eat(b']') {
+            loop {
+                if parser.eat_null() {
+                    lexicon.reserve_symbol();
+                } else {
+                    let string = parser.string()?;
+                    push(&mut lexicon, &string)?;
+                }
+                if parser.eat(b']') {
+                    break;
+                }
+                parser.expect(b',')?;
+            }
+        }
+        if parser.peek().is_some() {
+            return Err(parser.error("trailing characters"));
+        }
+        Ok(lexicon)
+    }
+
+    /// Returns the CSV export of this `Lexicon`.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("id,string\n");
+        for (id, string) in self.slots().enumerate() {
+            write!(csv, "{},", id).unwrap();
+            let string = match string {
+                Some(string) => string,
+                None => {
+                    csv.push('\n');
+                    continue;
+                }
+            };
+            if string.is_empty() || string.contains(&[',', '"', '\n', '\r'][..]) {
+                csv.push('"');
+                csv.push_str(&string.replace('"', "\"\""));
+                csv.push('"');
+            } else {
+                csv.push_str(string);
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+
+    pub fn write_csv(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(self.to_csv().as_bytes())
+    }
+
+    /// Reads a `Lexicon` from its CSV export, whose records must list ids
+    /// `0`, `1`, ... in order, each with a unique string, or an empty field
+    /// for a reserved symbol.
+    pub fn from_csv(csv: &str) -> io::Result<Self> {
+        let mut records = CsvRecords { rest: csv };
+        match records.next().transpose()? {
+            Some(header) if header == [Some("id".into()), Some("string".into())] => {}
+            _ => return Err(invalid_data("missing `id,string` CSV header")),
+        }
+        let mut lexicon = Lexicon::default();
+        for record in records {
+            let (id, string) = match &record?[..] {
+                [id, string] => (
+                    id.as_deref().and_then(|id| id.parse::<usize>().ok()),
+                    string.clone(),
+                ),
+                _ => return Err(invalid_data("CSV record is not `id,string`")),
+            };
+            if id != Some(lexicon.symbol_count()) {
+                return Err(invalid_data(format!(
+                    "expected id {} in CSV, found `{}`",
+                    lexicon.symbol_count(),
+                    id.map_or_else(String::new, |id| id.to_string())
+                )));
+            }
+            match string {
+                Some(string) => push(&mut lexicon, &string)?,
+                None => {
+                    lexicon.reserve_symbol();
+                }
+            }
+        }
+        Ok(lexicon)
+    }
+}
+
+/// Interns `string` as the next symbol of `lexicon`.
+fn push(lexicon: &mut Lexicon, string: &str) -> io::Result<()> {
+    let len = lexicon.symbol_count();
+    if lexicon.intern(string).as_usize() != len {
+        return Err(invalid_data(format!("duplicate string `{}`", string)));
+    }
+    Ok(())
+}
+
+/// Parser for the subset of JSON making up exported lexicons.
+struct JsonParser<'a> {
+    json: &'a str,
+    pos: usize,
+}
+
+impl JsonParser<'_> {
+    /// Returns the next byte which is not whitespace, without consuming it.
+    fn peek(&mut self) -> Option<u8> {
+        let bytes = self.json.as_bytes();
+        while bytes.get(self.pos).is_some_and(|b| b" \t\n\r".contains(b)) {
+            self.pos += 1;
+        }
+        bytes.get(self.pos).copied()
+    }
+
+    /// Consumes a `null` literal if it comes next.
+    fn eat_null(&mut self) -> bool {
+        let null = self.peek().is_some() && self.json[self.pos..].starts_with("null");
+        self.pos += 4 * null as usize;
+        null
+    }
+
+    /// Consumes `byte` if it comes next.
+    fn eat(&mut self, byte: u8) -> bool {
+        let next = self.peek() == Some(byte);
+        self.pos += next as usize;
+        next
+    }
+
+    fn expect(&mut self, byte: u8) -> io::Result<()> {
+        match self.eat(byte) {
+            true => Ok(()),
+            false => Err(self.error(format_args!("expected `{}`", byte as char))),
+        }
+    }
+
+    fn string(&mut self) -> io::Result<String> {
+        self.expect(b'"')?;
+        let mut string = String::new();
+        loop {
+            let rest = &self.json[self.pos..];
+            let end = rest
+                .find(|c: char| c == '"' || c == '\\' || c < ' ')
+                .ok_or_else(|| self.error("unterminated string"))?;
+            string.push_str(&rest[..end]);
+            self.pos += end + 1;
+            match rest.as_bytes()[end] {
+                b'"' => return Ok(string),
+                b'\\' => {}
+                _ => return Err(self.error("control character in string")),
+            }
+            let escape = self.json.as_bytes().get(self.pos).copied();
+            self.pos += 1;
+            string.push(match escape {
+                Some(b'"') => '"',
+                Some(b'\\') => '\\',
+                Some(b'/') => '/',
+                Some(b'b') => '\u{8}',
+                Some(b'f') => '\u{c}',
+                Some(b'n') => '\n',
+                Some(b'r') => '\r',
+                Some(b't') => '\t',
+                Some(b'u') => self.unicode_escape()?,
+                _ => return Err(self.error("invalid escape")),
+            });
+        }
+    }
+
+    /// Parses the rest of a `\u` escape, combining surrogate pairs.
+    fn unicode_escape(&mut self) -> io::Result<char> {
+        let high = self.hex()?;
+        let code = if (0xd800..0xdc00).contains(&high) {
+            if !self.json[self.pos..].starts_with("\\u") {
+                return Err(self.error("unpaired surrogate"));
+            }
+            self.pos += 2;
+            let low = self.hex()?;
+            if !(0xdc00..0xe000).contains(&low) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("unpaired surrogate"))
+    }
+
+    fn hex(&mut self) -> io::Result<u32> {
+        let digits = self.json.get(self.pos..self.pos + 4);
+        let code = digits
+            .filter(|digits| digits.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(code)
+    }
+
+    fn error(&self, msg: impl std::fmt::Display) -> io::Error {
+        invalid_data(format!("{} in JSON at byte {}", msg, self.pos))
+    }
+}
+
+/// Iterator over the records of a CSV document, as their unquoted fields,
+/// or `None` for empty fields which were not quoted.
+struct CsvRecords<'a> {
+    rest: &'a str,
+}
+
+impl Iterator for CsvRecords<'_> {
+    type Item = io::Result<Vec<Option<String>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let mut fields = vec![None];
+        let mut chars = self.rest.char_indices();
+        let mut quoted = false;
+        let end = loop {
+            let (i, c) = match chars.next() {
+                Some(next) => next,
+                None if quoted => return Some(Err(invalid_data("unterminated CSV quote"))),
+                None => break self.rest.len(),
+            };
+            let field = fields.last_mut().unwrap();
+            match c {
+                '"' if quoted && self.rest[i + 1..].starts_with('"') => {
+                    field.get_or_insert_with(String::new).push('"');
+                    chars.next();
+                }
+                '"' if quoted => quoted = false,
+                '"' if field.is_none() => {
+                    quoted = true;
+                    *field = Some(String::new());
+                }
+                ',' if !quoted => fields.push(None),
+                '\n' if !quoted => break i + 1,
+                '\r' if !quoted && self.rest[i + 1..].starts_with('\n') => break i + 2,
+                c => field.get_or_insert_with(String::new).push(c),
+            }
+        };
+        self.rest = &self.rest[end..];
+        Some(Ok(fields))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn lexicon() -> Lexicon {
+        let mut lexicon = [
+            "plain",
+            "with \"quotes\", commas",
+            "line\r\nbreak\t\u{1}",
+            "é🦀",
+            "",
+        ]
+        .iter()
+        .collect::<Lexicon>();
+        lexicon.reserve_symbol();
+        lexicon.intern("last");
+        lexicon
+    }
+
+    /// Asserts that `read` holds the same strings and reserved symbols as
+    /// `lexicon`.
+    fn assert_same(read: &Lexicon, lexicon: &Lexicon) {
+        assert!(read.slots().eq(lexicon.slots()));
+        read.validate();
+    }
+
+    #[test]
+    fn test_json() {
+        let lexicon = lexicon();
+        let json = lexicon.to_json();
+        let parsed = serde_json::from_str::<Vec<Option<String>>>(&json).unwrap();
+        assert_eq!(
+            parsed,
+            lexicon
+                .slots()
+                .map(|s| s.map(String::from))
+                .collect::<Vec<_>>()
+        );
+        assert_same(&Lexicon::from_json(&json).unwrap(), &lexicon);
+
+        let escaped = Lexicon::from_json(r#" [ "é🦀", "a\/b" ] "#).unwrap();
+        assert_eq!(
+            escaped.iter().map(|(_, s)| s).collect::<Vec<_>>(),
+            ["é🦀", "a/b"]
+        );
+        assert!(Lexicon::from_json("[]").unwrap().is_empty());
+        assert!(Lexicon::from_json(r#"["a", "a"]"#).is_err());
+        assert!(Lexicon::from_json(r#"["a",]"#).is_err());
+        assert!(Lexicon::from_json(r#"["\ud83e"]"#).is_err());
+        assert!(Lexicon::from_json(r#"["a"] x"#).is_err());
+    }
+
+    #[test]
+    fn test_csv() {
+        let lexicon = lexicon();
+        let csv = lexicon.to_csv();
+        assert!(csv.starts_with("id,string\n0,plain\n1,\"with \"\"quotes\"\", commas\"\n"));
+        assert!(csv.ends_with("4,\"\"\n5,\n6,last\n"));
+        assert_same(&Lexicon::from_csv(&csv).unwrap(), &lexicon);
+
+        let crlf = Lexicon::from_csv("id,string\r\n0,a\r\n1,\"b\"\r\n").unwrap();
+        assert_eq!(crlf.iter().map(|(_, s)| s).collect::<Vec<_>>(), ["a", "b"]);
+        assert!(Lexicon::from_csv("0,a\n").is_err());
+        assert!(Lexicon::from_csv("id,string\n1,a\n").is_err());
+        assert!(Lexicon::from_csv("id,string\n0,\"a\n").is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use crate::{Backend, Key, Lexicon};
+
+/// Number of times each key was interned, if tracked (see
+/// `Lexicon::track_frequencies`), indexed by key.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Frequencies {
+    counts: Option<Vec<u64>>,
+}
+
+impl Frequencies {
+    /// Counts an occurrence of the string at `idx`.
+    pub fn hit(&mut self, idx: usize) {
+        if let Some(counts) = &mut self.counts {
+            counts[idx] += 1;
+        }
+    }
+
+    /// Adds a new key, occurring `count` times so far.
+    pub fn push(&mut self, count: u64) {
+        if let Some(counts) = &mut self.counts {
+            counts.push(count);
+        }
+    }
+
+    pub fn set(&mut self, idx: usize, count: u64) {
+        if let Some(counts) = &mut self.counts {
+            counts[idx] = count;
+        }
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        if let Some(counts) = &mut self.counts {
+            counts.truncate(len);
+        }
+    }
+
+    /// Keeps the counts of the keys `kept` holds `true` for, in order.
+    pub fn retain(&mut self, kept: &[bool]) {
+        if let Some(counts) = &mut self.counts {
+            let mut kept = kept.iter();
+            counts.retain(|_| kept.next() == Some(&true));
+        }
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        if let Some(counts) = &mut self.counts {
+            counts.shrink_to_fit();
+        }
+    }
+}
+
+impl<K: Key, H: BuildHasher, B: Backend> Lexicon<K, H, B> {
+    /// Starts counting how many times each string is interned, whether new
+    /// or already present (as well as defined, see `define`), e.g., to find
+    /// the hottest identifiers of a corpus. Strings interned so far start
+    /// out at zero. Does nothing if already counting.
+    pub fn track_frequencies(&mut self) {
+        if self.frequencies.counts.is_none() {
+            self.frequencies.counts = Some(alloc::vec![0; self.vec.len()]);
+        }
+    }
+
+    /// Whether `track_frequencies` was called.
+    pub fn tracks_frequencies(&self) -> bool {
+        self.frequencies.counts.is_some()
+    }
+
+    /// Returns the number of times the string of `id` was interned since
+    /// `track_frequencies` was called, or `None` if not counting (or if
+    /// `id` was not interned in this `Lexicon`).
+    pub fn frequency(&self, id: K) -> Option<u64> {
+        self.frequencies
+            .counts
+            .as_ref()?
+            .get(id.to_usize())
+            .copied()
+    }
+
+    /// Returns the (at most) `n` most frequently interned keys along with
+    /// their counts, most frequent first, and in order of key among equally
+    /// frequent ones. Empty if not counting (see `track_frequencies`).
+    pub fn top_n(&self, n: usize) -> Vec<(K, u64)> {
+        let counts = match &self.frequencies.counts {
+            Some(counts) => counts,
+            None => return Vec::new(),
+        };
+        let mut top = self
+            .iter()
+            .map(|(id, _)| (id, counts[id.to_usize()]))
+            .collect::<Vec<_>>();
+        let by_count = |&(a, x): &(K, u64), &(b, y): &(K, u64)| {
+            y.cmp(&x).then(a.to_usize().cmp(&b.to_usize()))
+        };
+        if n < top.len() {
+            top.select_nth_unstable_by(n, by_count);
+            top.truncate(n);
+        }
+        top.sort_unstable_by(by_count);
+        top
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Symbol;
+
+    #[test]
+    fn test_frequencies() {
+        let mut lexicon = Lexicon::<Symbol>::default();
+        let a = lexicon.intern("a");
+        assert_eq!(lexicon.frequency(a), None);
+        assert!(lexicon.top_n(1).is_empty());
+
+        lexicon.track_frequencies();
+        for string in ["b", "a", "c", "b", "a", "b"] {
+            lexicon.intern(string);
+        }
+        let (b, c) = (lexicon.get("b").unwrap(), lexicon.get("c").unwrap());
+        assert_eq!(lexicon.frequency(a), Some(2));
+        assert_eq!(lexicon.top_n(2), [(b, 3), (a, 2)]);
+        assert_eq!(lexicon.top_n(10), [(b, 3), (a, 2), (c, 1)]);
+
+        let checkpoint = lexicon.checkpoint();
+        lexicon.intern("d");
+        lexicon.truncate_to(checkpoint);
+        lexicon.retain(|_, string| string != "a");
+        assert_eq!(
+            lexicon.top_n(10),
+            [(Symbol::new(0), 3), (Symbol::new(1), 1)]
+        );
+    }
+}
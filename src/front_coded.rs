@@ -0,0 +1,214 @@
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::mem::size_of;
+
+use crate::{Lexicon, Symbol};
+
+/// Read-only, compressed snapshot of a `Lexicon`, obtained via
+/// `Lexicon::freeze_front_coded`.
+///
+/// Like a `FrozenLexicon`, but storing its strings in lexicographic order
+/// with front coding: within each bucket of `BUCKET` consecutive strings,
+/// every string but the first only stores the suffix following the prefix
+/// it shares with the previous one. Large sets of identifiers sharing
+/// common prefixes (e.g., module paths, or mangled names) thus take a
+/// fraction of their plain size, at the cost of decoding (part of) a bucket
+/// to resolve a symbol. Strings are hence returned as owned `String`s, or
+/// written into a caller-provided buffer.
+#[derive(Clone)]
+pub struct FrontCodedLexicon {
+    inner: Arc<FrontCoded>,
+}
+
+struct FrontCoded {
+    /// Every bucket, one after another. The first string of a bucket is
+    /// encoded as its length followed by its bytes, and every other as the
+    /// length of the prefix it shares with the previous one, followed by
+    /// the length of the rest of it and its bytes. Lengths are LEB128.
+    data: Vec<u8>,
+    /// Start of every bucket within `data`.
+    buckets: Vec<usize>,
+    /// Position of every symbol's string in lexicographic order.
+    ranks: Vec<u32>,
+}
+
+impl FrontCodedLexicon {
+    /// Number of strings front coded together, trading lookup speed (as
+    /// half a bucket is decoded on average) for compression.
+    pub const BUCKET: usize = 16;
+
+    /// Returns the string of `id`.
+    ///
+    /// # Panics
+    /// Panics if `id` was not interned in the `Lexicon` this was frozen from.
+    pub fn lookup(&self, id: Symbol) -> String {
+        match self.try_lookup(id) {
+            Some(string) => string,
+            None => panic!("symbol `{}` was not interned", id.as_u32()),
+        }
+    }
+
+    pub fn try_lookup(&self, id: Symbol) -> Option<String> {
+        let mut buf = String::new();
+        self.lookup_into(id, &mut buf).then_some(buf)
+    }
+
+    /// Writes the string of `id` into `buf` (replacing its contents),
+    /// returning `false` if `id` was not interned in the `Lexicon` this was
+    /// frozen from. Reusing `buf` across lookups avoids allocating for each.
+    pub fn lookup_into(&self, id: Symbol, buf: &mut String) -> bool {
+        let FrontCoded {
+            data,
+            buckets,
+            ranks,
+        } = &*self.inner;
+        let rank = match ranks.get(id.as_usize()) {
+            Some(&rank) => rank as usize,
+            None => return false,
+        };
+        let mut pos = buckets[rank / Self::BUCKET];
+        buf.clear();
+        for i in 0..=rank % Self::BUCKET {
+            let shared = if i == 0 { 0 } else { read_len(data, &mut pos) };
+            let len = read_len(data, &mut pos);
+            let suffix = &data[pos..pos + len];
+            pos += len;
+            buf.truncate(shared);
+            // SAFETY: prefixes are only shared up to char boundaries, so
+            // every suffix is valid UTF-8 on its own
+            buf.push_str(unsafe { core::str::from_utf8_unchecked(suffix) });
+        }
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.ranks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.ranks.is_empty()
+    }
+
+    /// Returns the number of bytes taken by the encoded strings and their
+    /// index, e.g., to compare against the plain size of the strings.
+    pub fn encoded_size(&self) -> usize {
+        let FrontCoded {
+            data,
+            buckets,
+            ranks,
+        } = &*self.inner;
+        data.len() + buckets.len() * size_of::<usize>() + ranks.len() * size_of::<u32>()
+    }
+}
+
+impl Lexicon {
+    /// Freezes this `Lexicon` into a `FrontCodedLexicon`, compressing its
+    /// strings. Every `Symbol` interned in it remains valid for the returned
+    /// lexicon.
+    ///
+    /// # Panics
+    /// Panics if this `Lexicon` holds more than `u32::MAX` strings.
+    pub fn freeze_front_coded(self) -> FrontCodedLexicon {
+        // every symbol is encoded, even reserved ones (as empty strings)
+        let slots = self.slots().enumerate();
+        let mut sorted = slots
+            .map(|(i, string)| (Symbol::new(i as u32), string.unwrap_or("")))
+            .collect::<Vec<_>>();
+        sorted.sort_unstable_by_key(|&(_, string)| string);
+        let mut ranks = alloc::vec![0; self.symbol_count()];
+        let mut data = Vec::new();
+        let mut buckets =
+            Vec::with_capacity(self.symbol_count().div_ceil(FrontCodedLexicon::BUCKET));
+        let mut prev = "";
+        for (rank, &(id, string)) in sorted.iter().enumerate() {
+            ranks[id.as_usize()] = u32::try_from(rank).expect("too many strings to front code");
+            let mut shared = 0;
+            if rank % FrontCodedLexicon::BUCKET == 0 {
+                buckets.push(data.len());
+            } else {
+                let common = prev.bytes().zip(string.bytes()).take_while(|(a, b)| a == b);
+                shared = common.count();
+                while !string.is_char_boundary(shared) {
+                    shared -= 1;
+                }
+                write_len(&mut data, shared);
+            }
+            write_len(&mut data, string.len() - shared);
+            data.extend_from_slice(&string.as_bytes()[shared..]);
+            prev = string;
+        }
+        data.shrink_to_fit();
+        FrontCodedLexicon {
+            inner: Arc::new(FrontCoded {
+                data,
+                buckets,
+                ranks,
+            }),
+        }
+    }
+}
+
+fn write_len(data: &mut Vec<u8>, mut len: usize) {
+    while len >= 0x80 {
+        data.push(len as u8 | 0x80);
+        len >>= 7;
+    }
+    data.push(len as u8);
+}
+
+fn read_len(data: &[u8], pos: &mut usize) -> usize {
+    let (mut len, mut shift) = (0, 0);
+    loop {
+        let byte = data[*pos];
+        *pos += 1;
+        len |= ((byte & 0x7f) as usize) << shift;
+        if byte < 0x80 {
+            return len;
+        }
+        shift += 7;
+    }
+}
+
+impl fmt::Debug for FrontCodedLexicon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries((0..self.len()).map(|i| self.lookup(Symbol::new(i as u32))))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn test_front_coded() {
+        let strings = (0..100)
+            .map(|i| format!("std::collections::hash_map::Entry{}", i * 7 % 100))
+            .chain(["", "é", "éa", "è"].map(String::from))
+            .collect::<Vec<_>>();
+        let mut lexicon = strings.iter().collect::<Lexicon>();
+        let reserved = lexicon.reserve_symbol();
+        let plain = strings.iter().map(|s| s.len()).sum::<usize>();
+        let ids = strings
+            .iter()
+            .map(|s| lexicon.get(s).unwrap())
+            .collect::<Vec<_>>();
+
+        let front_coded = lexicon.freeze_front_coded();
+        assert_eq!(front_coded.len(), strings.len() + 1);
+        assert_eq!(front_coded.lookup(reserved), "");
+        let mut buf = String::new();
+        for (id, string) in ids.iter().zip(&strings) {
+            assert_eq!(&front_coded.lookup(*id), string);
+            assert!(front_coded.lookup_into(*id, &mut buf));
+            assert_eq!(&buf, string);
+        }
+        assert!(front_coded.encoded_size() < plain / 2);
+        assert_eq!(front_coded.try_lookup(Symbol::new(1000)), None);
+    }
+}
@@ -0,0 +1,165 @@
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Lexicon, Resolver, Symbol, Symbolic};
+
+/// Read-only snapshot of a `Lexicon`, obtained via `Lexicon::freeze`.
+///
+/// As a frozen lexicon can no longer intern new strings, it drops the
+/// string-to-`Symbol` table altogether and stores all of its strings in a
+/// single contiguous allocation. It is `Send + Sync`, and cloning it only
+/// bumps a reference count, making it suitable for resolving symbols from
+/// many threads once all interning is done (e.g., after parsing).
+#[derive(Clone)]
+pub struct FrozenLexicon {
+    inner: Arc<Frozen>,
+}
+
+struct Frozen {
+    data: String,
+    /// String `i` spans `data[ends[i - 1]..ends[i]]`, with `ends[-1]` being 0.
+    ends: Vec<usize>,
+}
+
+impl Lexicon {
+    /// Freezes this `Lexicon`, compacting its strings. Every `Symbol`
+    /// interned in it remains valid for the returned `FrozenLexicon`, with
+    /// reserved symbols (see `reserve_symbol`) frozen as empty strings.
+    pub fn freeze(self) -> FrozenLexicon {
+        let total = self.iter().map(|(_, s)| s.len()).sum();
+        let mut data = String::with_capacity(total);
+        let mut ends = Vec::with_capacity(self.symbol_count());
+        for string in self.slots() {
+            data.push_str(string.unwrap_or(""));
+            ends.push(data.len());
+        }
+        FrozenLexicon {
+            inner: Arc::new(Frozen { data, ends }),
+        }
+    }
+}
+
+impl FrozenLexicon {
+    pub fn lookup(&self, id: Symbol) -> &str {
+        match self.try_lookup(id) {
+            Some(string) => string,
+            None => panic!("symbol `{}` was not interned", id.as_u32()),
+        }
+    }
+
+    /// Returns the string corresponding to `id`, or `None` if `id` was not
+    /// interned in the `Lexicon` this was frozen from.
+    pub fn try_lookup(&self, id: Symbol) -> Option<&str> {
+        let Frozen { data, ends } = &*self.inner;
+        let i = id.as_usize();
+        let start = if i == 0 { 0 } else { *ends.get(i - 1)? };
+        Some(&data[start..*ends.get(i)?])
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.ends.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.ends.is_empty()
+    }
+}
+
+impl<S: Symbolic> core::ops::Index<S> for FrozenLexicon {
+    type Output = str;
+
+    fn index(&self, index: S) -> &Self::Output {
+        self.lookup(index.get_symbol())
+    }
+}
+
+impl Resolver for FrozenLexicon {
+    fn resolve(&self, id: Symbol) -> &str {
+        self.lookup(id)
+    }
+
+    fn try_resolve(&self, id: Symbol) -> Option<&str> {
+        self.try_lookup(id)
+    }
+}
+
+impl fmt::Debug for FrozenLexicon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries((0..self.len()).map(|i| self.lookup(Symbol::new(i as u32))))
+            .finish()
+    }
+}
+
+#[cfg(feature = "fst")]
+impl FrozenLexicon {
+    /// Exports this lexicon as an FST mapping every string to its symbol
+    /// (see `Symbol::as_u32`), e.g., for compact, ordered and prefix-aware
+    /// lookups through the `fst` crate's automata and range queries.
+    ///
+    /// Strings occurring more than once (i.e., symbols reserved but never
+    /// defined, frozen as empty strings) map to their smallest symbol.
+    pub fn to_fst(&self) -> fst::Map<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.write_fst(&mut bytes)
+            .expect("writing into a `Vec` never fails");
+        fst::Map::new(bytes).expect("freshly built FST is valid")
+    }
+
+    /// Like `to_fst`, but writes the FST to `writer` (e.g., a file, to be
+    /// memory-mapped later) rather than building it in memory.
+    pub fn write_fst(&self, writer: impl std::io::Write) -> Result<(), fst::Error> {
+        let mut entries = (0..self.len() as u32)
+            .map(|id| (self.lookup(Symbol::new(id)), id))
+            .collect::<Vec<_>>();
+        entries.sort_unstable();
+        entries.dedup_by_key(|&mut (string, _)| string);
+
+        let mut builder = fst::MapBuilder::new(writer)?;
+        for (string, id) in entries {
+            builder.insert(string, id.into())?;
+        }
+        builder.finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_freeze() {
+        let mut lexicon = Lexicon::default();
+        let syms = ["", "frozen", "lexicon"].map(|s| lexicon.intern(s));
+        let frozen = lexicon.freeze();
+        assert_eq!(frozen.len(), 3);
+        assert_eq!(frozen.lookup(syms[0]), "");
+        assert_eq!(&frozen[syms[1]], "frozen");
+
+        let shared = frozen.clone();
+        let handle = std::thread::spawn(move || shared.lookup(syms[2]).to_string());
+        assert_eq!(handle.join().unwrap(), "lexicon");
+    }
+
+    #[cfg(feature = "fst")]
+    #[test]
+    fn test_to_fst() {
+        let mut lexicon = Lexicon::default();
+        let syms = ["b", "ab", "a"].map(|s| lexicon.intern(s));
+        lexicon.reserve_symbol();
+        lexicon.reserve_symbol();
+        let fst = lexicon.freeze().to_fst();
+        assert_eq!(fst.len(), 4);
+        assert_eq!(fst.get("ab"), Some(syms[1].as_u32().into()));
+        assert_eq!(fst.get(""), Some(3));
+        assert_eq!(fst.get("c"), None);
+        let mut keys = fst.keys();
+        let mut sorted = Vec::new();
+        while let Some(key) = fst::Streamer::next(&mut keys) {
+            sorted.push(String::from_utf8(key.to_vec()).unwrap());
+        }
+        assert_eq!(sorted, ["", "a", "ab", "b"]);
+    }
+}
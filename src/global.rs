@@ -0,0 +1,106 @@
+//! A single process-wide interner, along with free functions for interning
+//! into and resolving from it. Since the global interner is never dropped
+//! (nor ever cleared), strings resolved from it live for `'static`.
+
+use crate::{ConcurrentLexicon, Symbol, Symbolic};
+
+lazy_static::lazy_static! {
+    static ref INTERNER: ConcurrentLexicon = ConcurrentLexicon::new();
+}
+
+/// Interns a string slice with the global interner, returning its `Symbol`.
+pub fn intern(string: &str) -> Symbol {
+    INTERNER.intern(string)
+}
+
+/// Resolves a `Symbol` previously returned by the global interner. Unlike
+/// `lookup`, this does not allocate, as the global interner outlives every
+/// caller.
+pub fn resolve<S: Symbolic>(sym: S) -> &'static str {
+    INTERNER.lookup(sym.get_symbol())
+}
+
+pub(crate) fn try_resolve(sym: Symbol) -> Option<&'static str> {
+    INTERNER.try_lookup(sym)
+}
+
+pub fn intern_once<S: AsRef<str>>(s: S) -> Symbol {
+    INTERNER.intern(s.as_ref())
+}
+
+pub fn intern_all<S: AsRef<str>>(strings: impl Iterator<Item = S>) -> impl Iterator<Item = Symbol> {
+    strings.map(|s| INTERNER.intern(s.as_ref()))
+}
+
+pub fn intern_many<S: AsRef<str>, const N: usize>(strings: [S; N]) -> [Symbol; N] {
+    strings.map(|s| INTERNER.intern(s.as_ref()))
+}
+
+pub fn lookup<S: Symbolic>(sym: S) -> String {
+    resolve(sym).to_string()
+}
+
+pub fn lookup_many<S: Symbolic>(syms: &[S]) -> Vec<String> {
+    syms.iter()
+        .map(|s| resolve(s.get_symbol()).to_string())
+        .collect()
+}
+
+/// Interns a string literal (or any other `&'static str`) with the global
+/// interner only once per call site, caching its `Symbol` for every later
+/// evaluation. Meant for well-known names compared against in hot paths:
+///
+/// ```
+/// use lexicon::{intern, sym};
+///
+/// let name = intern("main");
+/// assert_eq!(name, sym!("main"));
+/// ```
+#[macro_export]
+macro_rules! sym {
+    ($string:expr) => {{
+        static SYMBOL: ::std::sync::OnceLock<$crate::Symbol> = ::std::sync::OnceLock::new();
+        *SYMBOL.get_or_init(|| $crate::intern($string))
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_intern_once() {
+        let word = "hello";
+        let sym = intern_once(word);
+        println!("{} : {}", word, sym);
+    }
+
+    #[test]
+    fn test_parallel() {
+        use std::thread;
+
+        let hi = "hi";
+        let bye = "bye";
+
+        let syms_1 = intern_many([hi, bye]);
+        let syms_2 = thread::spawn(move || intern_many(["hi", "bye"]))
+            .join()
+            .unwrap();
+
+        assert_eq!(syms_1, syms_2)
+    }
+
+    #[test]
+    fn test_sym_macro() {
+        let syms = (0..3).map(|_| sym!("cached")).collect::<Vec<_>>();
+        assert!(syms.iter().all(|&s| s == intern("cached")));
+        assert_ne!(sym!("cached"), sym!("other"));
+    }
+
+    #[test]
+    fn test_resolve_static() {
+        let resolved: &'static str = resolve(intern("static"));
+        assert_eq!(resolved, "static");
+        assert_eq!(intern(resolved), intern("static"));
+    }
+}
@@ -0,0 +1,98 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Key, Resolver, Symbol};
+
+/// Interner handing out keys of type `K`, against which generic code (e.g.,
+/// a lexer) may be written independently of the concrete interner.
+///
+/// Interning is fallible, failing with the interner's own `Error` type (such
+/// as `LexiconError` for the interners of this crate) rather than panicking,
+/// while resolving keys is provided by the `Resolver` supertrait. Keys are
+/// taken and returned by value. Everything but `try_intern`, `get` and `len`
+/// has a default implementation in terms of these.
+pub trait Interner<K: Key = Symbol>: Resolver<K> {
+    type Error: fmt::Display;
+
+    /// Returns the key of `string`, interning it first if it is new, or an
+    /// error if it cannot be interned.
+    fn try_intern(&mut self, string: &str) -> Result<K, Self::Error>;
+
+    /// Returns the key of `string` if it has already been interned.
+    fn get(&self, string: &str) -> Option<K>;
+
+    /// Returns the number of strings interned so far.
+    fn len(&self) -> usize;
+
+    /// Like `try_intern`, but panics if `string` cannot be interned.
+    fn intern(&mut self, string: &str) -> K {
+        match self.try_intern(string) {
+            Ok(id) => id,
+            Err(e) => panic!("{} while interning `{}`", e, string),
+        }
+    }
+
+    /// Returns the string corresponding to `id`, or `None` if `id` was not
+    /// interned (see `Resolver::try_resolve`).
+    fn try_lookup(&self, id: K) -> Option<&str> {
+        self.try_resolve(id)
+    }
+
+    /// Whether `string` has already been interned.
+    fn contains(&self, string: &str) -> bool {
+        self.get(string).is_some()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Interns every string of `strings`, returning their keys in order, or
+    /// the first error encountered.
+    fn try_intern_all<S: AsRef<str>>(
+        &mut self,
+        strings: impl IntoIterator<Item = S>,
+    ) -> Result<Vec<K>, Self::Error>
+    where
+        Self: Sized,
+    {
+        let strings = strings.into_iter();
+        strings
+            .map(|string| self.try_intern(string.as_ref()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lexicon, LexiconError};
+
+    fn tokenize<K: Key, I: Interner<K>>(
+        interner: &mut I,
+        source: &str,
+    ) -> Result<Vec<K>, I::Error> {
+        interner.try_intern_all(source.split_whitespace())
+    }
+
+    #[test]
+    fn test_generic_interner() {
+        let mut lexicon = Lexicon::<u16>::with_key_capacity(8);
+        let tokens = tokenize(&mut lexicon, "a b a");
+        assert_eq!(tokens, Ok(alloc::vec![0, 1, 0]));
+        assert_eq!(Interner::get(&lexicon, "b"), Some(1));
+        assert_eq!(Interner::try_lookup(&lexicon, 1), Some("b"));
+        assert!(Interner::contains(&lexicon, "a"));
+        assert_eq!(Interner::len(&lexicon), 2);
+    }
+
+    #[test]
+    fn test_interner_error() {
+        let mut lexicon = Lexicon::<u16>::with_key_capacity(8);
+        let source = (0..=u16::MAX as u32 + 1)
+            .map(|i| alloc::format!("{} ", i))
+            .collect::<Vec<_>>();
+        let tokens = tokenize(&mut lexicon, &source.concat());
+        assert_eq!(tokens, Err(LexiconError::KeysExhausted));
+    }
+}
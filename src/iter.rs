@@ -0,0 +1,246 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use core::hash::BuildHasher;
+use core::iter::{Enumerate, FromIterator, FusedIterator};
+use core::marker::PhantomData;
+use core::slice;
+
+use hashbrown::HashSet;
+
+use crate::arena::Arena;
+use crate::{Backend, Key, Lexicon, Symbol};
+
+/// Iterator over the `(Symbol, &str)` pairs of a `Lexicon`, in the order they
+/// were interned. Created by `Lexicon::iter`.
+///
+/// Keys reserved but not defined yet (see `Lexicon::reserve_symbol`) have
+/// no string, and are skipped.
+#[derive(Clone, Debug)]
+pub struct Iter<'a, K = Symbol, B: Backend = Arena> {
+    inner: Enumerate<slice::Iter<'a, B::Span>>,
+    backend: &'a B,
+    pending: &'a HashSet<usize>,
+    /// Number of strings left, i.e., excluding reserved keys.
+    remaining: usize,
+    key: PhantomData<K>,
+}
+
+impl<'a, K: Key, B: Backend> Iterator for Iter<'a, K, B> {
+    type Item = (K, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pending = self.pending;
+        let (i, &s) = self.inner.find(|(i, _)| !pending.contains(i))?;
+        self.remaining -= 1;
+        Some((key(i), self.backend.get(s)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K: Key, B: Backend> DoubleEndedIterator for Iter<'_, K, B> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let pending = self.pending;
+        let (i, &s) = self.inner.rfind(|(i, _)| !pending.contains(i))?;
+        self.remaining -= 1;
+        Some((key(i), self.backend.get(s)))
+    }
+}
+
+impl<K: Key, B: Backend> ExactSizeIterator for Iter<'_, K, B> {}
+
+impl<K: Key, B: Backend> FusedIterator for Iter<'_, K, B> {}
+
+/// Every index iterated over belongs to an interned string, and thus has a
+/// valid key.
+fn key<K: Key>(idx: usize) -> K {
+    K::try_from_usize(idx).expect("interned string has no key")
+}
+
+/// Owning iterator over the strings of a `Lexicon`, in the order they were
+/// interned. Created by `Lexicon::into_iter`. Like `Iter`, skips reserved
+/// keys.
+#[derive(Debug)]
+pub struct IntoIter<B: Backend = Arena> {
+    spans: Enumerate<vec::IntoIter<B::Span>>,
+    backend: B,
+    pending: HashSet<usize>,
+    remaining: usize,
+}
+
+impl<B: Backend> Iterator for IntoIter<B> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pending = &self.pending;
+        let (_, s) = self.spans.find(|(i, _)| !pending.contains(i))?;
+        self.remaining -= 1;
+        Some(self.backend.get(s).to_string())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<B: Backend> DoubleEndedIterator for IntoIter<B> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let pending = &self.pending;
+        let (_, s) = self.spans.rfind(|(i, _)| !pending.contains(i))?;
+        self.remaining -= 1;
+        Some(self.backend.get(s).to_string())
+    }
+}
+
+impl<B: Backend> ExactSizeIterator for IntoIter<B> {}
+
+impl<B: Backend> FusedIterator for IntoIter<B> {}
+
+impl<'a, K: Key, H: BuildHasher, B: Backend> IntoIterator for &'a Lexicon<K, H, B> {
+    type Item = (K, &'a str);
+    type IntoIter = Iter<'a, K, B>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, H, B: Backend> IntoIterator for Lexicon<K, H, B> {
+    type Item = String;
+    type IntoIter = IntoIter<B>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            remaining: self.vec.len() - self.pending.len(),
+            spans: self.vec.into_iter().enumerate(),
+            backend: self.backend,
+            pending: self.pending,
+        }
+    }
+}
+
+/// Interns every string of the iterator, in order. Duplicates are simply
+/// mapped to the `Symbol` of their first occurrence.
+impl<K, H, B, S> FromIterator<S> for Lexicon<K, H, B>
+where
+    K: Key,
+    H: BuildHasher + Default,
+    B: Backend,
+    S: AsRef<str>,
+{
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut lexicon = Lexicon::with_hasher_and_backend(
+            H::default(),
+            B::with_capacity(Lexicon::BASE_CAPACITY),
+        );
+        lexicon.extend(iter);
+        lexicon
+    }
+}
+
+impl<K: Key, H: BuildHasher, B: Backend, S: AsRef<str>> Extend<S> for Lexicon<K, H, B> {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for string in iter {
+            self.intern(string.as_ref());
+        }
+    }
+}
+
+impl<K: Key, H: BuildHasher, B: Backend> Lexicon<K, H, B> {
+    /// Returns an iterator over every interned string along with its
+    /// `Symbol`, in the order they were interned, i.e., `len` entries.
+    /// Reserved keys are skipped.
+    pub fn iter(&self) -> Iter<'_, K, B> {
+        Iter {
+            inner: self.vec.iter().enumerate(),
+            backend: &self.backend,
+            pending: &self.pending,
+            remaining: self.len(),
+            key: PhantomData,
+        }
+    }
+
+    /// Returns the string of every key in order, or `None` for reserved
+    /// ones, such that the `i`-th item is that of key `i` (e.g., for
+    /// encodings restoring every key as is).
+    pub(crate) fn slots(&self) -> impl ExactSizeIterator<Item = Option<&str>> + Clone + '_ {
+        let strings = self.vec.iter().enumerate();
+        strings.map(move |(i, &s)| (!self.pending.contains(&i)).then(|| self.backend.get(s)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_iter() {
+        let mut lexicon = Lexicon::default();
+        let words = ["c", "a", "b"];
+        let syms = words.map(|w| lexicon.intern(w));
+
+        assert_eq!(lexicon.len(), 3);
+        assert_eq!(lexicon.symbol_count(), 3);
+        assert!(!lexicon.is_empty());
+        assert_eq!(
+            lexicon.iter().collect::<Vec<_>>(),
+            syms.iter().copied().zip(words).collect::<Vec<_>>()
+        );
+        assert_eq!(lexicon.iter().next_back(), Some((syms[2], "b")));
+        assert!(Lexicon::default().iter().next().is_none());
+    }
+
+    #[test]
+    fn test_iter_skips_reserved() {
+        let mut lexicon = Lexicon::default();
+        let reserved = lexicon.reserve_symbol();
+        let a = lexicon.intern("a");
+        lexicon.reserve_symbol();
+        assert_eq!(lexicon.iter().collect::<Vec<_>>(), [(a, "a")]);
+        assert_eq!(lexicon.iter().len(), 1);
+        assert_eq!(lexicon.iter().count(), lexicon.len());
+        assert_eq!(lexicon.symbol_count(), 3);
+        assert_eq!(lexicon.iter().next_back(), Some((a, "a")));
+        assert_eq!(lexicon.slots().collect::<Vec<_>>(), [None, Some("a"), None]);
+
+        lexicon.define(reserved, "b");
+        assert_eq!(lexicon.len(), 2);
+        assert_eq!(
+            lexicon.iter().map(|(_, s)| s).collect::<Vec<_>>(),
+            ["b", "a"]
+        );
+        assert_eq!(lexicon.into_iter().collect::<Vec<_>>(), ["b", "a"]);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let mut lexicon = Lexicon::with_capacity(1);
+        for word in ["x", "yy", "zzz"] {
+            lexicon.intern(word);
+        }
+
+        let mut lengths = 0;
+        for (_, string) in &lexicon {
+            lengths += string.len();
+        }
+        assert_eq!(lengths, 6);
+
+        let owned = lexicon.into_iter().collect::<Vec<String>>();
+        assert_eq!(owned, ["x", "yy", "zzz"]);
+    }
+
+    #[test]
+    fn test_from_iter_extend() {
+        let keywords = ["fn", "let", "fn", "if"];
+        let mut lexicon = keywords.iter().collect::<Lexicon>();
+        assert_eq!(lexicon.len(), 3);
+        assert_eq!(lexicon.get("if"), Some(Symbol::new(2)));
+
+        lexicon.extend(vec![String::from("else"), String::from("let")]);
+        assert_eq!(lexicon.len(), 4);
+        assert_eq!(lexicon.get("else"), Some(Symbol::new(3)));
+    }
+}
@@ -0,0 +1,173 @@
+use core::convert::TryFrom;
+use core::fmt::{self, Debug};
+use core::hash::Hash;
+use core::num::{NonZeroU16, NonZeroU32, NonZeroU64};
+
+use crate::Symbol;
+
+/// Type of the keys a `Lexicon` hands out for its interned strings. Keys are
+/// allocated sequentially, so a key is simply the index of its string within
+/// the `Lexicon`; the smaller the key type, the fewer strings a `Lexicon`
+/// may hold, but the smaller the data structures storing its keys.
+///
+/// Implemented for `Symbol` (the default), its narrower and wider variants
+/// `Symbol16` and `Symbol64`, as well as for `u16`, `u32` and `usize`, and
+/// for the keys of the other interners (`ByteSymbol`, `OsSymbol` and
+/// `PathSymbol`).
+pub trait Key: Copy + Eq + Hash + Debug {
+    /// Returns the key for the string at index `idx`, or `None` if `idx`
+    /// cannot be represented by this key type.
+    fn try_from_usize(idx: usize) -> Option<Self>;
+
+    /// Returns the index of the string corresponding to this key.
+    fn to_usize(self) -> usize;
+}
+
+impl Key for Symbol {
+    fn try_from_usize(idx: usize) -> Option<Self> {
+        u32::try_from(idx).ok().and_then(Symbol::try_new)
+    }
+
+    fn to_usize(self) -> usize {
+        self.as_usize()
+    }
+}
+
+macro_rules! impl_key {
+    ($($t:ty),*) => {$(
+        impl Key for $t {
+            fn try_from_usize(idx: usize) -> Option<Self> {
+                <$t>::try_from(idx).ok()
+            }
+
+            fn to_usize(self) -> usize {
+                self as usize
+            }
+        }
+    )*};
+}
+
+impl_key!(u16, u32, usize);
+
+macro_rules! symbol_variant {
+    ($(#[$attr:meta])* $name:ident($nonzero:ty, $int:ty), $as_int:ident) => {
+        $(#[$attr])*
+        ///
+        /// Like `Symbol`, it is stored off by one, such that an `Option` of it
+        /// takes up no more space than the key itself.
+        #[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+        pub struct $name($nonzero);
+
+        impl $name {
+            pub fn $as_int(&self) -> $int {
+                self.0.get() - 1
+            }
+
+            pub fn as_usize(&self) -> usize {
+                self.$as_int() as usize
+            }
+        }
+
+        impl Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}({})", stringify!($name), self.$as_int())
+            }
+        }
+
+        impl Key for $name {
+            fn try_from_usize(idx: usize) -> Option<Self> {
+                let idx = <$int>::try_from(idx).ok()?;
+                <$nonzero>::new(idx.wrapping_add(1)).map($name)
+            }
+
+            fn to_usize(self) -> usize {
+                self.as_usize()
+            }
+        }
+    };
+}
+
+symbol_variant! {
+    /// Compact `Key` for small tables of at most `u16::MAX` strings, e.g., the
+    /// identifiers of a DSL, whose keys are packed into dense structures.
+    Symbol16(NonZeroU16, u16), as_u16
+}
+
+symbol_variant! {
+    /// Wide `Key` for tables which may exceed `u32::MAX` strings.
+    Symbol64(NonZeroU64, u64), as_u64
+}
+
+symbol_variant! {
+    /// `Key` of the byte strings interned by a `ByteLexicon`, kept distinct
+    /// from `Symbol` so the two are never mixed up.
+    ByteSymbol(NonZeroU32, u32), as_u32
+}
+
+symbol_variant! {
+    /// `Key` of the paths interned by a `PathLexicon`.
+    OsSymbol(NonZeroU32, u32), as_u32
+}
+
+symbol_variant! {
+    /// `Key` of the symbol sequences interned by a `PathInterner`.
+    PathSymbol(NonZeroU32, u32), as_u32
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Lexicon;
+
+    #[test]
+    fn test_u16_keys() {
+        let mut lexicon = Lexicon::<u16>::with_key_capacity(Lexicon::BASE_CAPACITY);
+        let syms = ["a", "b", "a"].map(|s| lexicon.intern(s));
+        assert_eq!(syms, [0, 1, 0]);
+        assert_eq!(lexicon.lookup(1), "b");
+        assert_eq!(lexicon.try_lookup(2), None);
+        assert_eq!(u16::try_from_usize(1 << 16), None);
+        lexicon.validate();
+    }
+
+    #[test]
+    fn test_symbol_variants() {
+        use core::mem::size_of;
+        assert_eq!(size_of::<Option<Symbol16>>(), 2);
+        assert_eq!(size_of::<Option<Symbol64>>(), 8);
+
+        let mut lexicon = Lexicon::<Symbol16>::with_key_capacity(16);
+        let sym = lexicon.intern("dsl");
+        assert_eq!(sym.as_u16(), 0);
+        assert_eq!(lexicon.lookup(sym), "dsl");
+        assert_eq!(Symbol16::try_from_usize(u16::MAX as usize), None);
+        assert_eq!(
+            Symbol64::try_from_usize(1 << 40).map(Key::to_usize),
+            Some(1 << 40)
+        );
+    }
+
+    #[test]
+    fn test_try_intern() {
+        let mut lexicon = Lexicon::<u16>::with_key_capacity(Lexicon::BASE_CAPACITY);
+        for i in 0..=u16::MAX as u32 {
+            lexicon.try_intern(&i.to_string()).unwrap();
+        }
+        assert_eq!(lexicon.try_intern("0"), Ok(0));
+        assert_eq!(
+            lexicon.try_intern("full"),
+            Err(crate::LexiconError::KeysExhausted)
+        );
+        assert_eq!(lexicon.get("full"), None);
+        lexicon.validate();
+    }
+
+    #[test]
+    #[should_panic(expected = "lexicon keys exhausted")]
+    fn test_key_space_exhausted() {
+        let mut lexicon = Lexicon::<u16>::with_key_capacity(Lexicon::BASE_CAPACITY);
+        for i in 0..=u16::MAX as u32 + 1 {
+            lexicon.intern(&i.to_string());
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use core::hash::BuildHasher;
+
+use crate::{Backend, Key, Lexicon};
+
+impl Lexicon {
+    /// Creates a new `Lexicon` holding `keywords` (in order) as its first
+    /// strings, such that `is_keyword` tells them apart from any string
+    /// interned later on.
+    pub fn with_keywords(keywords: &[&'static str]) -> Self {
+        let mut lexicon = Lexicon::default();
+        lexicon.intern_keywords(keywords.iter().copied());
+        lexicon
+    }
+}
+
+impl<K: Key, H: BuildHasher, B: Backend> Lexicon<K, H, B> {
+    /// Interns `keywords`, extending the range of keys `is_keyword` holds
+    /// for. Keywords are stored without being copied (see `intern_static`).
+    ///
+    /// # Panics
+    /// Panics if any string other than a keyword was interned before.
+    pub fn intern_keywords(&mut self, keywords: impl IntoIterator<Item = &'static str>) {
+        assert_eq!(
+            self.symbol_count(),
+            self.keywords,
+            "keywords must be interned before any other string"
+        );
+        for keyword in keywords {
+            self.intern_static(keyword);
+        }
+        self.keywords = self.symbol_count();
+    }
+
+    /// Returns the number of keywords, i.e., the index every key of a
+    /// keyword is below.
+    pub fn keyword_limit(&self) -> usize {
+        self.keywords
+    }
+
+    /// Whether `id` is the key of a keyword interned through
+    /// `intern_keywords` (or `with_keywords`).
+    pub fn is_keyword(&self, id: K) -> bool {
+        id.to_usize() < self.keywords
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_keywords() {
+        let mut lexicon = Lexicon::with_keywords(&["fn", "let", "fn"]);
+        assert_eq!(lexicon.keyword_limit(), 2);
+        let ident = lexicon.intern("x");
+        let kw = lexicon.intern("let");
+        assert!(lexicon.is_keyword(kw));
+        assert!(!lexicon.is_keyword(ident));
+
+        let checkpoint = lexicon.checkpoint();
+        lexicon.clear();
+        assert_eq!(lexicon.keyword_limit(), 0);
+        assert!(lexicon.try_truncate_to(checkpoint).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "keywords must be interned before any other string")]
+    fn test_keywords_after_strings() {
+        let mut lexicon = Lexicon::default();
+        lexicon.intern("x");
+        lexicon.intern_keywords(["fn"]);
+    }
+}
@@ -0,0 +1,172 @@
+use alloc::sync::Arc;
+
+use crate::{Interner, Lexicon, LexiconError, Resolver, Symbol, Symbolic};
+
+/// Interner layered on top of a shared, read-only parent `Lexicon` (e.g., a
+/// keyword table common to every compilation unit).
+///
+/// Strings already present in the parent resolve to the parent's symbols,
+/// while new strings are interned into a local layer, whose symbols are
+/// numbered after the parent's so that the two never collide. The parent is
+/// thus only ever interned into once, no matter how many layers share it.
+#[derive(Clone, Debug)]
+pub struct LayeredLexicon {
+    parent: Arc<Lexicon>,
+    local: Lexicon,
+}
+
+impl LayeredLexicon {
+    pub fn new(parent: Arc<Lexicon>) -> Self {
+        Self {
+            parent,
+            local: Lexicon::default(),
+        }
+    }
+
+    pub fn parent(&self) -> &Arc<Lexicon> {
+        &self.parent
+    }
+
+    /// Returns the offset of the local layer's symbols, i.e., the number of
+    /// strings in the parent. Since the parent is shared (and hence
+    /// immutable), this never changes.
+    fn base(&self) -> u32 {
+        self.parent.len() as u32
+    }
+
+    pub fn intern(&mut self, string: &str) -> Symbol {
+        match self.try_intern(string) {
+            Ok(id) => id,
+            Err(e) => panic!("{} while interning `{}`", e, string),
+        }
+    }
+
+    /// Like `intern`, but returns an error rather than panicking if `string`
+    /// cannot be interned into the local layer.
+    pub fn try_intern(&mut self, string: &str) -> Result<Symbol, LexiconError> {
+        if let Some(id) = self.parent.get(string) {
+            return Ok(id);
+        }
+        let local = self.local.try_intern(string)?.as_u32();
+        self.base()
+            .checked_add(local)
+            .and_then(Symbol::try_new)
+            .ok_or(LexiconError::KeysExhausted)
+    }
+
+    pub fn get(&self, string: &str) -> Option<Symbol> {
+        self.parent
+            .get(string)
+            .or_else(|| Some(Symbol::new(self.base() + self.local.get(string)?.as_u32())))
+    }
+
+    pub fn lookup(&self, id: Symbol) -> &str {
+        match self.try_lookup(id) {
+            Some(string) => string,
+            None => panic!("symbol `{}` was not interned", id.as_u32()),
+        }
+    }
+
+    pub fn try_lookup(&self, id: Symbol) -> Option<&str> {
+        match id.as_u32().checked_sub(self.base()) {
+            None => self.parent.try_lookup(id),
+            Some(local) => self.local.try_lookup(Symbol::new(local)),
+        }
+    }
+
+    /// Whether `id` belongs to the parent rather than to the local layer.
+    pub fn is_inherited(&self, id: Symbol) -> bool {
+        id.as_u32() < self.base()
+    }
+
+    /// Returns the number of strings across both the parent and the local
+    /// layer.
+    pub fn len(&self) -> usize {
+        self.parent.len() + self.local.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Flattens both layers into a single `Lexicon`, in which every `Symbol`
+    /// of this `LayeredLexicon` remains valid.
+    pub fn into_lexicon(self) -> Lexicon {
+        let mut lexicon = Arc::try_unwrap(self.parent).unwrap_or_else(|parent| {
+            let mut lexicon = Lexicon::with_capacity(Lexicon::BASE_CAPACITY);
+            for string in parent.slots() {
+                match string {
+                    Some(string) => lexicon.intern(string),
+                    None => lexicon.reserve_symbol(),
+                };
+            }
+            lexicon
+        });
+        lexicon.extend(self.local.iter().map(|(_, s)| s));
+        lexicon
+    }
+}
+
+impl<S: Symbolic> core::ops::Index<S> for LayeredLexicon {
+    type Output = str;
+
+    fn index(&self, index: S) -> &Self::Output {
+        self.lookup(index.get_symbol())
+    }
+}
+
+impl Resolver for LayeredLexicon {
+    fn resolve(&self, id: Symbol) -> &str {
+        self.lookup(id)
+    }
+
+    fn try_resolve(&self, id: Symbol) -> Option<&str> {
+        self.try_lookup(id)
+    }
+}
+
+impl Interner for LayeredLexicon {
+    type Error = LexiconError;
+
+    fn try_intern(&mut self, string: &str) -> Result<Symbol, LexiconError> {
+        self.try_intern(string)
+    }
+
+    fn get(&self, string: &str) -> Option<Symbol> {
+        self.get(string)
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_layered() {
+        let keywords = Arc::new(["fn", "let"].iter().collect::<Lexicon>());
+        let mut unit_a = LayeredLexicon::new(Arc::clone(&keywords));
+        let mut unit_b = LayeredLexicon::new(Arc::clone(&keywords));
+
+        let kw = unit_a.intern("let");
+        assert_eq!(kw, keywords.get("let").unwrap());
+        assert!(unit_a.is_inherited(kw));
+        assert_eq!(unit_b.intern("let"), kw);
+
+        let a = unit_a.intern("a");
+        let b = unit_b.intern("b");
+        assert_eq!(a, Symbol::new(2));
+        assert_eq!(b, Symbol::new(2));
+        assert_eq!(&unit_a[a], "a");
+        assert_eq!(&unit_b[b], "b");
+        assert_eq!(unit_a.get("b"), None);
+        assert_eq!(keywords.len(), 2);
+
+        let flat = unit_a.into_lexicon();
+        assert_eq!(flat.lookup(kw), "let");
+        assert_eq!(flat.lookup(a), "a");
+    }
+}
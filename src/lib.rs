@@ -1,35 +1,231 @@
-use std::collections::HashMap;
-use std::mem;
-use std::sync::{Arc, Mutex};
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+
+extern crate alloc;
+// lets `#[derive(Symbolic)]` refer to `::lexicon` from within this crate
+#[cfg(all(test, feature = "derive"))]
+extern crate self as lexicon;
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::BuildHasher;
+use core::num::NonZeroU32;
+
+use hashbrown::{DefaultHashBuilder, HashSet, HashTable};
+
+mod alias;
+mod arena;
+mod ascii;
+pub mod backend;
+#[cfg(feature = "std")]
+pub mod binary;
+mod branded;
+#[cfg(feature = "bumpalo")]
+mod bump;
+mod bytes;
+mod canonical;
+mod caseless;
+mod checkpoint;
+#[cfg(feature = "std")]
+mod concurrent;
+mod diff;
+mod display;
+mod error;
+#[cfg(feature = "std")]
+mod export;
+mod frequency;
+mod front_coded;
+mod frozen;
+#[cfg(feature = "global")]
+mod global;
+mod interner;
+mod iter;
+mod key;
+mod keywords;
+mod layered;
+#[cfg(feature = "std")]
+pub mod local;
+mod lru;
+#[cfg(feature = "std")]
+mod mapped;
+mod memory;
+#[cfg(feature = "unicode")]
+mod nfc;
+mod normalize;
+mod observe;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "std")]
+mod path;
+mod pattern;
+mod predefined;
+mod prefix;
+mod refcount;
+mod remap;
+mod reserve;
+mod resolver;
+#[cfg(feature = "rkyv")]
+mod rkyv_impls;
+mod sequence;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "std")]
+mod sharded;
+mod side_table;
+#[cfg(feature = "std")]
+mod slots;
+mod sorted;
+mod stable;
+#[cfg(feature = "stats")]
+mod stats;
+#[cfg(feature = "std")]
+mod stream;
+mod suggest;
+pub mod symbol_map;
+mod symbol_set;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub mod symbol_str;
+mod tagged;
+mod trie;
+mod value;
+mod writer;
+
+use arena::Arena;
+
+pub use ascii::AsciiLexicon;
+pub use backend::Backend;
+pub use branded::BrandedSymbol;
+#[cfg(feature = "bumpalo")]
+pub use bump::BumpLexicon;
+pub use bytes::ByteLexicon;
+pub use caseless::CaselessLexicon;
+pub use checkpoint::Checkpoint;
+#[cfg(feature = "std")]
+pub use concurrent::ConcurrentLexicon;
+pub use diff::LexiconDiff;
+pub use error::LexiconError;
+pub use front_coded::FrontCodedLexicon;
+pub use frozen::FrozenLexicon;
+#[cfg(feature = "global")]
+pub use global::{intern, intern_all, intern_many, intern_once, lookup, lookup_many, resolve};
+pub use interner::Interner;
+pub use iter::{IntoIter, Iter};
+pub use key::{ByteSymbol, Key, OsSymbol, PathSymbol, Symbol16, Symbol64};
+pub use layered::LayeredLexicon;
+pub use lru::{LruLexicon, LruSymbol};
+#[cfg(feature = "std")]
+pub use mapped::MappedLexicon;
+pub use memory::MemoryStats;
+#[cfg(feature = "unicode")]
+pub use nfc::{Nfc, NfcLexicon};
+pub use normalize::{Canonicalizer, Lowercase, NormalizedLexicon, Normalizer, Trim};
+#[cfg(feature = "std")]
+pub use path::PathLexicon;
+pub use pattern::{Glob, Matcher};
+pub use predefined::Predefined;
+pub use refcount::RcLexicon;
+pub use remap::SymbolRemap;
+pub use resolver::Resolver;
+#[cfg(feature = "rkyv")]
+pub use rkyv_impls::ArchivedLexicon;
+pub use sequence::PathInterner;
+#[cfg(feature = "serde")]
+pub use serde_impls::{SymbolSeed, SymbolSerializer};
+#[cfg(feature = "std")]
+pub use sharded::ShardedLexicon;
+pub use side_table::SideTable;
+pub use sorted::SortedIndex;
+pub use stable::{StableLexicon, StableSymbol};
+#[cfg(feature = "stats")]
+pub use stats::InternStats;
+pub use symbol_map::SymbolMap;
+pub use symbol_set::SymbolSet;
+pub use tagged::TaggedSymbol;
+pub use value::ValueInterner;
+pub use writer::SymbolWriter;
+
+#[cfg(feature = "derive")]
+pub use lexicon_derive::Symbolic;
+
+#[cfg(feature = "ahash")]
+pub use ahash::RandomState as AHashBuilder;
+#[cfg(feature = "fxhash")]
+pub use rustc_hash::FxBuildHasher;
 
 /// Key used to reference stored strings. When a string is interened, a
 /// `Symbol` is returned, which can then be used to retrieve the original
 /// string representation. This helps reduce the footprint of data structures
 /// containing *immutable* strings like variable names, string literals, etc.
+///
+/// Symbols are stored off by one in a `NonZeroU32`, such that an
+/// `Option<Symbol>` takes up no more space than a `Symbol`.
 #[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
-pub struct Symbol(u32);
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(derive(Clone, Copy, Debug, PartialEq, Eq, Hash), compare(PartialEq))
+)]
+pub struct Symbol(NonZeroU32);
+
+/// With the `global` feature enabled, symbols are displayed as the string
+/// they resolve to in the global interner. Otherwise, as there is no interner
+/// to resolve them with, they are displayed as `$N`.
+impl fmt::Display for Symbol {
+    #[cfg(feature = "global")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", resolve(*self))
+    }
 
-impl std::fmt::Display for Symbol {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.display())
+    #[cfg(not(feature = "global"))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "${}", self.as_u32())
     }
 }
 
-impl std::fmt::Debug for Symbol {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Symbol({}: `{}`)", self.0, self)
+/// Symbols not (or not yet) interned in the global interner, e.g., those of
+/// a local `Lexicon`, are debug-printed without their string.
+impl fmt::Debug for Symbol {
+    #[cfg(feature = "global")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match global::try_resolve(*self) {
+            Some(string) => write!(f, "Symbol({}: `{}`)", self.as_u32(), string),
+            None => write!(f, "Symbol({})", self.as_u32()),
+        }
+    }
+
+    #[cfg(not(feature = "global"))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Symbol({})", self.as_u32())
     }
 }
 
 impl Symbol {
-    pub fn as_u32(&self) -> u32 {
-        self.0
+    /// Returns the symbol with index `idx`, or `None` for `u32::MAX`, which
+    /// no symbol may have.
+    pub(crate) fn try_new(idx: u32) -> Option<Symbol> {
+        NonZeroU32::new(idx.wrapping_add(1)).map(Symbol)
+    }
+
+    pub(crate) fn new(idx: u32) -> Symbol {
+        match Symbol::try_new(idx) {
+            Some(sym) => sym,
+            None => panic!("symbol index `{}` is out of range", idx),
+        }
+    }
+
+    pub const fn as_u32(&self) -> u32 {
+        self.0.get() - 1
     }
 
     pub fn as_usize(&self) -> usize {
-        self.0 as usize
+        self.as_u32() as usize
     }
 
+    #[cfg(feature = "global")]
     pub fn display(self) -> String {
         lookup(self)
     }
@@ -55,9 +251,21 @@ impl Symbolic for Symbol {
     }
 }
 
+macro_rules! forward_symbolic {
+    ($($t:ty),*) => {$(
+        impl<T: Symbolic + ?Sized> Symbolic for $t {
+            fn get_symbol(&self) -> Symbol {
+                (**self).get_symbol()
+            }
+        }
+    )*};
+}
+
+forward_symbolic!(&T, &mut T, Box<T>, Rc<T>, Arc<T>);
+
 impl From<Symbol> for usize {
-    fn from(Symbol(i): Symbol) -> Self {
-        i as usize
+    fn from(sym: Symbol) -> Self {
+        sym.as_usize()
     }
 }
 
@@ -65,12 +273,72 @@ impl From<Symbol> for usize {
 /// process, all strings are instead interned and mapped to instances of type
 /// `Symbol`, which unlike `&str` and `String`, are [`Copy`] and additionally
 /// more lightweight.
+///
+/// The type of the keys handed out may be changed from `Symbol` to any other
+/// `Key`, e.g., `Lexicon<u16>` for small tables whose keys are stored in
+/// densely packed structures.
+///
+/// Strings are hashed with `H`, which defaults to `hashbrown`'s default
+/// hasher. Faster (but not DoS resistant) hashers, such as `FxBuildHasher`
+/// or `AHashBuilder`, are available through the `fxhash` and `ahash`
+/// features respectively, and may be passed to `Lexicon::with_hasher`.
+///
+/// Strings are stored in `B`, which defaults to a `BucketBackend`; see the
+/// `backend` module for the available backends and their trade-offs.
+///
+/// Cloning a `Lexicon` is cheap: its tables are copied, while its string
+/// storage is reference counted and shared with the clone. Since strings are
+/// only ever referred to by their position within that storage, the clone
+/// and the original are fully independent of each other afterwards (see
+/// `Lexicon::fork`).
+///
+/// A `Lexicon` is `Send` and `Sync` (as long as `K` and `H` are), so it may
+/// be moved into worker threads, or shared between them while only being
+/// read from.
 #[derive(Clone, Debug)]
-struct Lexicon {
-    map: HashMap<&'static str, Symbol>,
-    vec: Vec<&'static str>,
-    buf: String,
-    all: Vec<String>,
+pub struct Lexicon<K = Symbol, H = DefaultHashBuilder, B: Backend = Arena> {
+    /// Keys of every interned string, hashed by their string (as stored in
+    /// `vec`), such that a string is only ever hashed once when interning.
+    map: HashTable<K>,
+    hasher: H,
+    /// Location of every interned string within `backend`, indexed by key.
+    vec: Vec<B::Span>,
+    backend: B,
+    scopes: Vec<Checkpoint<B::Mark>>,
+    /// Indices of the keys handed out by `reserve_symbol` but not defined
+    /// yet, whose spans in `vec` are empty placeholders.
+    pending: HashSet<usize>,
+    /// Indices of the reserved keys defined since, whose strings may have
+    /// been stored after those of later keys.
+    defined: HashSet<usize>,
+    /// Number of keywords, which are always the first strings interned.
+    keywords: usize,
+    /// Alternative spellings registered through `alias`, along with the key
+    /// they resolve to.
+    aliases: Vec<(B::Span, K)>,
+    /// Indices into `aliases`, hashed by their spelling.
+    alias_map: HashTable<usize>,
+    /// Buffer strings are formatted into before being interned, kept around
+    /// to reuse its allocation.
+    scratch: String,
+    /// Trie of every (defined) string, if maintained (see `index_prefixes`).
+    prefixes: Option<trie::Trie<K>>,
+    /// Maximum total length of the stored strings, if limited (see
+    /// `set_memory_limit`).
+    limit: Option<usize>,
+    /// Total length of the stored strings, as counted against `limit`.
+    stored: usize,
+    #[cfg(feature = "stats")]
+    stats: stats::InternStats,
+    /// Callbacks for newly interned strings (see `on_intern`).
+    observers: observe::Observers<K>,
+    /// Tables of per-key values, kept in sync with `vec` (see `side_table`).
+    side_tables: side_table::SideTables,
+    /// Number of times each key was interned, if tracked (see
+    /// `track_frequencies`).
+    frequencies: frequency::Frequencies,
+    /// Lexicographic order of the keys, once needed (see `sorted_index`).
+    sorted: sorted::SortedCache<K>,
 }
 
 impl Lexicon {
@@ -78,60 +346,457 @@ impl Lexicon {
     /// This could/should maybe be optimized later.
     pub const BASE_CAPACITY: usize = 64;
 
+    /// Length assumed of the average string when sizing tables for a given
+    /// number of bytes (see `with_capacity_and_hasher`), typical of
+    /// identifiers.
+    pub const AVERAGE_LEN: usize = 8;
+
     pub fn with_capacity(cap: usize) -> Self {
-        let cap = cap.next_power_of_two();
+        Self::with_key_capacity(cap)
+    }
+}
+
+impl<H: BuildHasher> Lexicon<Symbol, H> {
+    /// Creates a new `Lexicon` hashing its strings with `hasher`.
+    pub fn with_hasher(hasher: H) -> Self {
+        Self::with_capacity_and_hasher(Lexicon::BASE_CAPACITY, hasher)
+    }
+}
+
+impl<K: Key> Lexicon<K> {
+    /// Creates a new `Lexicon` handing out keys of type `K` rather than
+    /// `Symbol`, e.g., `Lexicon::<u16>::with_key_capacity(256)`.
+    pub fn with_key_capacity(cap: usize) -> Self {
+        Self::with_capacity_and_hasher(cap, DefaultHashBuilder::default())
+    }
+}
+
+impl<B: Backend> Lexicon<Symbol, DefaultHashBuilder, B> {
+    /// Creates a new `Lexicon` storing its strings in `backend` (see the
+    /// `backend` module), e.g., `Lexicon::with_backend(LeakedBackend::default())`.
+    pub fn with_backend(backend: B) -> Self {
+        Self::with_hasher_and_backend(DefaultHashBuilder::default(), backend)
+    }
+}
+
+impl<K: Key, H: BuildHasher> Lexicon<K, H> {
+    /// Creates a new `Lexicon` with room for `cap` bytes of strings, and
+    /// for as many strings as that typically holds (see `AVERAGE_LEN`).
+    /// Use `reserve` to make room for a known number of strings instead.
+    pub fn with_capacity_and_hasher(cap: usize, hasher: H) -> Self {
+        let mut lexicon = Self::with_hasher_and_backend(hasher, Arena::with_capacity(cap));
+        lexicon.reserve(cap / Lexicon::AVERAGE_LEN, 0);
+        lexicon
+    }
+}
+
+impl<K: Key, H: BuildHasher, B: Backend> Lexicon<K, H, B> {
+    pub fn with_hasher_and_backend(hasher: H, backend: B) -> Self {
         Self {
-            map: HashMap::default(),
+            map: HashTable::new(),
+            hasher,
             vec: Vec::new(),
-            buf: String::with_capacity(cap),
-            all: Vec::new(),
+            backend,
+            scopes: Vec::new(),
+            pending: HashSet::new(),
+            defined: HashSet::new(),
+            keywords: 0,
+            aliases: Vec::new(),
+            alias_map: HashTable::new(),
+            scratch: String::new(),
+            prefixes: None,
+            limit: None,
+            stored: 0,
+            #[cfg(feature = "stats")]
+            stats: stats::InternStats::default(),
+            observers: observe::Observers::default(),
+            side_tables: side_table::SideTables::default(),
+            frequencies: frequency::Frequencies::default(),
+            sorted: sorted::SortedCache::default(),
         }
     }
 
-    pub fn intern(&mut self, string: &str) -> Symbol {
-        if let Some(&id) = self.map.get(string) {
-            return id;
+    /// # Panics
+    /// Panics if `string` is new, but every key of type `K` is already taken.
+    /// See `try_intern` for a non-panicking alternative.
+    pub fn intern(&mut self, string: &str) -> K {
+        match self.try_intern(string) {
+            Ok(id) => id,
+            Err(e) => panic!("{} while interning `{}`", e, string),
         }
+    }
 
-        let string = unsafe { self.alloc(string) };
-        let id = Symbol(self.map.len() as u32);
+    /// Like `intern`, but returns an error rather than panicking if `string`
+    /// is new and either every key of type `K` is taken
+    /// (`LexiconError::KeysExhausted`), it is longer than the backend allows
+    /// (`LexiconError::StringTooLong`), or storing it would exceed the memory
+    /// limit (`LexiconError::MemoryLimitExceeded`, see `set_memory_limit`).
+    pub fn try_intern(&mut self, string: &str) -> Result<K, LexiconError> {
+        self.intern_with(string, |backend, string| backend.alloc(string))
+            .map_err(|(e, _)| e)
+    }
 
-        self.map.insert(string, id);
-        self.vec.push(string);
+    /// Like `intern`, but stores a reference to `string` itself rather than
+    /// copying it (if the backend supports it; see `Backend::alloc_static`),
+    /// e.g., for keyword tables or sources embedded with `include_str!`.
+    pub fn intern_static(&mut self, string: &'static str) -> K {
+        match self.intern_with(string, |backend, string| backend.alloc_static(string)) {
+            Ok(id) => id,
+            Err((e, _)) => panic!("{} while interning `{}`", e, string),
+        }
+    }
 
-        debug_assert!(self.lookup(id) == string);
-        debug_assert!(self.intern(string) == id);
+    /// Like `intern`, but takes ownership of `string`, which is stored as is
+    /// rather than copied if new (if the backend supports it; see
+    /// `Backend::alloc_owned`), e.g., for names built up with `format!`.
+    pub fn intern_owned(&mut self, string: String) -> K {
+        match self.intern_with(string, |backend, string| backend.alloc_owned(string)) {
+            Ok(id) => id,
+            Err((e, string)) => panic!("{} while interning `{}`", e, string),
+        }
+    }
 
+    /// Returns the key of `string` if it has already been interned, or
+    /// interns the string built by `make` otherwise, which must be equal to
+    /// `string`. As with `intern_static` and `intern_owned`, the built string
+    /// is stored without being copied where possible.
+    ///
+    /// This lets callers holding `string` only in a temporary buffer (e.g.,
+    /// a lexer's) skip building an owned (or `'static`) copy of it
+    /// whenever it is already present.
+    pub fn get_or_intern_with(
+        &mut self,
+        string: &str,
+        make: impl FnOnce() -> Cow<'static, str>,
+    ) -> K {
+        let hash = self.hasher.hash_one(string);
+        if let Some(id) = self.find(hash, string) {
+            #[cfg(feature = "stats")]
+            {
+                self.stats.hits += 1;
+            }
+            self.frequencies.hit(id.to_usize());
+            return id;
+        }
+        let made = make();
+        debug_assert_eq!(made, string, "`make` built a different string");
+        let result = self.intern_hashed(hash, made, |backend, made| match made {
+            Cow::Borrowed(made) => backend.alloc_static(made),
+            Cow::Owned(made) => backend.alloc_owned(made),
+        });
+        match result {
+            Ok(id) => id,
+            Err((e, _)) => panic!("{} while interning `{}`", e, string),
+        }
+    }
+
+    /// Interns the string `args` formats to, e.g.,
+    /// `lexicon.intern_fmt(format_args!("__tmp{}", n))`. The string is
+    /// formatted into a buffer reused across calls, rather than into a newly
+    /// allocated `String`.
+    pub fn intern_fmt(&mut self, args: fmt::Arguments<'_>) -> K {
+        if let Some(string) = args.as_str() {
+            return self.intern_static(string);
+        }
+        let mut buf = core::mem::take(&mut self.scratch);
+        buf.clear();
+        if fmt::Write::write_fmt(&mut buf, args).is_err() {
+            panic!("a formatting trait implementation returned an error");
+        }
+        let id = self.intern(&buf);
+        self.scratch = buf;
         id
     }
 
-    pub fn lookup(&self, id: Symbol) -> &str {
-        self.vec[id.as_u32() as usize]
+    /// Interns the concatenation of the strings of `parts`, e.g., for name
+    /// mangling. The string is built in the same reused buffer as by
+    /// `intern_fmt`.
+    ///
+    /// # Panics
+    /// Panics if any of `parts` was not interned in this `Lexicon`.
+    pub fn concat_symbols(&mut self, parts: &[K]) -> K {
+        self.concat_symbols_with(parts, "")
     }
 
-    unsafe fn alloc(&mut self, string: &str) -> &'static str {
-        let cap = self.buf.capacity();
-        if cap < self.buf.len() + string.len() {
-            // just doubling isn't enough -- need to ensure the new string
-            // actually fits
-            let new_cap = (cap.max(string.len()) + 1).next_power_of_two();
-            let new_buf = String::with_capacity(new_cap);
-            let old_buf = mem::replace(&mut self.buf, new_buf);
-            self.all.push(old_buf);
+    /// Like `concat_symbols`, but separates the strings of `parts` with
+    /// `sep`, e.g., to flatten module paths (see also `join`).
+    pub fn concat_symbols_with(&mut self, parts: &[K], sep: &str) -> K {
+        let mut buf = core::mem::take(&mut self.scratch);
+        buf.clear();
+        for (i, &part) in parts.iter().enumerate() {
+            if i > 0 {
+                buf.push_str(sep);
+            }
+            buf.push_str(self.lookup(part));
         }
+        let id = self.intern(&buf);
+        self.scratch = buf;
+        id
+    }
+
+    /// Interns the string `value` is displayed as (see `intern_fmt`).
+    pub fn intern_display(&mut self, value: &impl fmt::Display) -> K {
+        self.intern_fmt(format_args!("{}", value))
+    }
 
-        let interned = {
-            let start = self.buf.len();
-            self.buf.push_str(string);
-            &self.buf[start..]
+    /// Interns every string of `strings` (e.g., a batch of tokens), returning
+    /// their keys in order.
+    pub fn intern_all<S: AsRef<str>>(&mut self, strings: impl IntoIterator<Item = S>) -> Vec<K> {
+        let mut ids = Vec::new();
+        self.intern_all_into(strings, &mut ids);
+        ids
+    }
+
+    /// Like `intern_all`, but appends the keys to `ids`, whose allocation
+    /// may thus be reused across batches.
+    pub fn intern_all_into<S: AsRef<str>>(
+        &mut self,
+        strings: impl IntoIterator<Item = S>,
+        ids: &mut Vec<K>,
+    ) {
+        let strings = strings.into_iter();
+        ids.reserve(strings.size_hint().0);
+        ids.extend(strings.map(|string| self.intern(string.as_ref())));
+    }
+
+    /// Splits `path` on every occurrence of `sep` (e.g., `"::"`), interning
+    /// each segment in order and appending their keys to `ids`. Empty
+    /// segments, such as those around a leading or trailing separator, are
+    /// interned as the empty string.
+    ///
+    /// Nothing is allocated other than for new segments, and by `ids`
+    /// itself, which may thus be a reused `Vec`, or a vector storing a few
+    /// keys inline (e.g., `SmallVec<[Symbol; 4]>`) for paths usually made of
+    /// a few segments.
+    ///
+    /// # Panics
+    /// Panics if `sep` is empty.
+    pub fn intern_path(&mut self, path: &str, sep: &str, ids: &mut impl Extend<K>) {
+        assert!(!sep.is_empty(), "path separator must not be empty");
+        ids.extend(path.split(sep).map(|segment| self.intern(segment)));
+    }
+
+    /// Interns `string`, storing it in the backend through `alloc` only if
+    /// it is new. The string is hashed only once either way, and handed back
+    /// along with the error on failure.
+    fn intern_with<S: AsRef<str>>(
+        &mut self,
+        string: S,
+        alloc: impl FnOnce(&mut B, S) -> B::Span,
+    ) -> Result<K, (LexiconError, S)> {
+        let hash = self.hasher.hash_one(string.as_ref());
+        self.intern_hashed(hash, string, alloc)
+    }
+
+    /// Like `intern_with`, given the `hash` of `string` as computed by the
+    /// `Lexicon`'s hasher.
+    fn intern_hashed<S: AsRef<str>>(
+        &mut self,
+        hash: u64,
+        string: S,
+        alloc: impl FnOnce(&mut B, S) -> B::Span,
+    ) -> Result<K, (LexiconError, S)> {
+        if let Some(id) = self.find(hash, string.as_ref()) {
+            #[cfg(feature = "stats")]
+            {
+                self.stats.hits += 1;
+            }
+            self.frequencies.hit(id.to_usize());
+            return Ok(id);
+        }
+
+        let id = match K::try_from_usize(self.vec.len()) {
+            Some(id) => id,
+            None => return Err((LexiconError::KeysExhausted, string)),
         };
+        if string.as_ref().len() > B::MAX_LEN {
+            return Err((LexiconError::StringTooLong, string));
+        }
+        if let Err(e) = self.charge(string.as_ref().len()) {
+            return Err((e, string));
+        }
+        #[cfg(feature = "stats")]
+        let (len, capacities) = (
+            string.as_ref().len(),
+            (self.map.capacity(), self.vec.capacity()),
+        );
+        #[cfg(feature = "tracing")]
+        if string.as_ref().len() > Arena::CHUNK_SIZE {
+            tracing::debug!(len = string.as_ref().len(), "interning large string");
+        }
+        let span = alloc(&mut self.backend, string);
+        self.vec.push(span);
+        self.side_tables.push_default();
+        self.frequencies.push(1);
+        self.sorted.invalidate();
+
+        // the table may need to grow, rehashing the strings already in it
+        #[cfg(feature = "tracing")]
+        let _rehash = (self.map.len() == self.map.capacity())
+            .then(|| tracing::debug_span!("lexicon_rehash", len = self.map.len()).entered());
+        let (vec, backend, hasher) = (&self.vec, &self.backend, &self.hasher);
+        self.map.insert_unique(hash, id, |id| {
+            hasher.hash_one(backend.get(vec[id.to_usize()]))
+        });
+        self.index_prefix(id);
+        self.notify(id);
+        #[cfg(feature = "stats")]
+        {
+            self.stats.misses += 1;
+            self.stats.bytes_allocated += len as u64;
+            self.stats.rehashes += (self.map.capacity() != capacities.0) as u64;
+            self.stats.vec_growths += (self.vec.capacity() != capacities.1) as u64;
+        }
+
+        Ok(id)
+    }
+
+    /// Returns the `Symbol` of `string` if it has already been interned.
+    /// Unlike `intern`, this never adds `string` to the `Lexicon`.
+    pub fn get(&self, string: &str) -> Option<K> {
+        self.find(self.hasher.hash_one(string), string)
+    }
+
+    fn find(&self, hash: u64, string: &str) -> Option<K> {
+        let (vec, backend) = (&self.vec, &self.backend);
+        let id = self
+            .map
+            .find(hash, |id| backend.get(vec[id.to_usize()]) == string);
+        id.copied().or_else(|| self.find_alias(hash, string))
+    }
+
+    pub fn lookup(&self, id: K) -> &str {
+        match self.lookup_checked(id) {
+            Ok(string) => string,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Like `lookup`, but returns `LexiconError::InvalidKey` rather than
+    /// panicking if `id` was not interned in this `Lexicon`.
+    pub fn lookup_checked(&self, id: K) -> Result<&str, LexiconError> {
+        self.try_lookup(id)
+            .ok_or(LexiconError::InvalidKey(id.to_usize()))
+    }
+
+    /// Returns the string corresponding to `id`, or `None` if `id` was not
+    /// interned in this `Lexicon`. Prefer this over `lookup` for symbols
+    /// coming from untrusted (e.g., deserialized) sources.
+    pub fn try_lookup(&self, id: K) -> Option<&str> {
+        let string = self.backend.get(*self.vec.get(id.to_usize())?);
+        if string.is_empty() && self.is_reserved(id) {
+            return None;
+        }
+        Some(string)
+    }
+
+    /// Creates a copy-on-write fork of this `Lexicon`. The fork shares all of
+    /// the string storage of `self` (which stays alive for as long as either
+    /// of them does), and only allocates storage for strings interned into
+    /// it after the fork. Every `Symbol` of `self` is valid for the fork, and
+    /// vice versa, up until either of them interns a new string.
+    pub fn fork(&self) -> Self
+    where
+        H: Clone,
+        B: Clone,
+    {
+        self.clone()
+    }
+
+    /// Removes every string from this `Lexicon`, invalidating all `Symbol`s
+    /// previously interned in it. The allocations of its tables, as well as
+    /// its largest string buffer, are kept for reuse, making this cheaper
+    /// than creating a new `Lexicon`.
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.vec.clear();
+        self.scopes.clear();
+        self.pending.clear();
+        self.defined.clear();
+        self.keywords = 0;
+        self.aliases.clear();
+        self.alias_map.clear();
+        if let Some(trie) = &mut self.prefixes {
+            trie.clear();
+        }
+        self.stored = 0;
+        self.side_tables.truncate(0);
+        self.frequencies.truncate(0);
+        self.sorted.invalidate();
+        self.backend.clear()
+    }
+
+    /// Returns the number of strings interned in this `Lexicon`, i.e., the
+    /// number of entries yielded by `iter`. Keys reserved but not defined
+    /// yet (see `reserve_symbol`) are not counted, unlike by `symbol_count`.
+    pub fn len(&self) -> usize {
+        self.vec.len() - self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of `Symbol`s handed out by this `Lexicon`,
+    /// including those reserved but not defined yet. Every `Symbol` below
+    /// this count is valid for this `Lexicon`.
+    pub fn symbol_count(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Whether `id` was interned in this `Lexicon`, i.e., whether it is
+    /// yielded by `iter`. Keys reserved but not defined yet (see
+    /// `reserve_symbol`) are not.
+    pub fn contains_sym(&self, id: K) -> bool {
+        id.to_usize() < self.vec.len() && !self.pending.contains(&id.to_usize())
+    }
 
-        &*(interned as *const str)
+    /// Checks the internal invariants of this `Lexicon`: that the string-to-
+    /// `Symbol` map and the `Symbol`-to-string table agree with each other,
+    /// and that every stored string points into this `Lexicon`'s own storage.
+    ///
+    /// # Panics
+    /// Panics with a description of the first violated invariant.
+    pub fn validate(&self) {
+        assert_eq!(
+            self.map.len() + self.pending.len(),
+            self.vec.len(),
+            "lexicon map and table have different lengths"
+        );
+        for (i, &span) in self.vec.iter().enumerate() {
+            if self.pending.contains(&i) {
+                continue;
+            }
+            assert!(
+                self.backend.owns(span),
+                "string of symbol `{}` is not owned by this lexicon",
+                i
+            );
+            let string = self.backend.get(span);
+            assert_eq!(
+                self.get(string).map(|id| id.to_usize()),
+                Some(i),
+                "string `{}` is not mapped to symbol `{}`",
+                string,
+                i
+            );
+        }
     }
 
     pub fn capacity(&self) -> usize {
-        #![allow(unused)]
-        self.buf.capacity()
+        self.backend.capacity()
+    }
+
+    /// Makes room for at least `symbols` more strings, spanning `bytes`
+    /// bytes in total, to be interned without growing any table (or string
+    /// buffer) in between, e.g., ahead of loading a dictionary.
+    pub fn reserve(&mut self, symbols: usize, bytes: usize) {
+        let (vec, backend, hasher) = (&self.vec, &self.backend, &self.hasher);
+        self.map.reserve(symbols, |id| {
+            hasher.hash_one(backend.get(vec[id.to_usize()]))
+        });
+        self.vec.reserve(symbols);
+        self.backend.reserve(bytes);
     }
 }
 
@@ -141,7 +806,7 @@ impl Default for Lexicon {
     }
 }
 
-impl<S: Symbolic> std::ops::Index<S> for Lexicon {
+impl<S: Symbolic> core::ops::Index<S> for Lexicon {
     type Output = str;
 
     fn index(&self, index: S) -> &Self::Output {
@@ -149,82 +814,59 @@ impl<S: Symbolic> std::ops::Index<S> for Lexicon {
     }
 }
 
-lazy_static::lazy_static! {
-    static ref INTERNER: Arc<Mutex<Lexicon>> = Arc::new(Mutex::new(Lexicon::default()));
-}
+impl<K: Key, H: BuildHasher, B: Backend> Resolver<K> for Lexicon<K, H, B> {
+    fn resolve(&self, id: K) -> &str {
+        self.lookup(id)
+    }
 
-pub fn intern_once<S: AsRef<str>>(s: S) -> Symbol {
-    match INTERNER.lock() {
-        Ok(mut guard) => guard.intern(s.as_ref()),
-        Err(poisoned) => {
-            eprintln!("{}", poisoned);
-            panic!("poisoned while interning `{}`", s.as_ref())
-        }
+    fn try_resolve(&self, id: K) -> Option<&str> {
+        self.try_lookup(id)
     }
 }
 
-pub fn intern_all<S: AsRef<str>>(strings: impl Iterator<Item = S>) -> impl Iterator<Item = Symbol> {
-    match INTERNER.lock() {
-        Ok(mut guard) => strings.map(move |s| guard.intern(s.as_ref())),
-        Err(e) => {
-            eprintln!("{}", e);
-            panic!(
-                "poisoned while interning `{}`",
-                &strings
-                    .map(|s| s.as_ref().to_string())
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            )
-        }
+impl<K: Key, H: BuildHasher, B: Backend> Interner<K> for Lexicon<K, H, B> {
+    type Error = LexiconError;
+
+    fn try_intern(&mut self, string: &str) -> Result<K, LexiconError> {
+        self.try_intern(string)
     }
-}
 
-pub fn intern_many<S: AsRef<str>, const N: usize>(strings: [S; N]) -> [Symbol; N] {
-    match INTERNER.lock() {
-        Ok(mut guard) => {
-            let mut syms = [Symbol(0); N];
-            for (i, s) in strings.iter().enumerate() {
-                syms[i] = guard.intern(s.as_ref());
-            }
-            syms
-        }
-        Err(e) => {
-            eprintln!("{}", e);
-            panic!(
-                "poisoned while interning `{:?}`",
-                &strings.iter().map(|s| s.as_ref()).collect::<Vec<_>>()
-            )
-        }
+    fn get(&self, string: &str) -> Option<K> {
+        self.get(string)
     }
-}
 
-pub fn lookup<S: Symbolic>(sym: S) -> String {
-    match INTERNER.lock() {
-        Ok(guard) => guard.lookup(sym.get_symbol()).to_string(),
-        Err(e) => {
-            eprintln!("{}", e);
-            panic!("poisoned while looking up symbol `{:?}`", sym.get_symbol())
-        }
+    fn len(&self) -> usize {
+        self.len()
     }
 }
 
-pub fn lookup_many<S: Symbolic>(syms: &[S]) -> Vec<String> {
-    match INTERNER.lock() {
-        Ok(guard) => syms
-            .into_iter()
-            .map(|s| guard.lookup(s.get_symbol()).to_string())
-            .collect(),
-        Err(e) => {
-            eprintln!("{}", e);
-            panic!(
-                "poisoned while looking up symbols `{:?}`",
-                syms.iter().map(|s| s.get_symbol()).collect::<Vec<_>>()
-            )
-        }
+// Every interner may be moved into (and shared between) worker threads: none
+// of them references its own storage through anything but indices, `Arc`s
+// and atomics. Checked at compile time so this never silently regresses.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Lexicon>();
+    assert_send_sync::<CaselessLexicon>();
+    assert_send_sync::<ByteLexicon>();
+    assert_send_sync::<StableLexicon>();
+    assert_send_sync::<RcLexicon>();
+    assert_send_sync::<LruLexicon>();
+    assert_send_sync::<Lexicon<TaggedSymbol<()>>>();
+    assert_send_sync::<FrozenLexicon>();
+    assert_send_sync::<LayeredLexicon>();
+    assert_send_sync::<Iter<'_>>();
+    assert_send_sync::<IntoIter>();
+    #[cfg(feature = "std")]
+    {
+        assert_send_sync::<ConcurrentLexicon>();
+        assert_send_sync::<ShardedLexicon>();
+        assert_send_sync::<PathLexicon>();
+        assert_send_sync::<MappedLexicon<Vec<u8>>>();
     }
-}
+};
 
 // Instantiating a thread local string interner
+#[cfg(feature = "std")]
 #[macro_export]
 macro_rules! local_lexicon {
     ($name:ident) => {
@@ -234,17 +876,39 @@ macro_rules! local_lexicon {
             /// Since this `Lexicon` is wrapped within a `RefCell`, accessing
             /// it *must* be done through the provided utility_functions in
             /// order to ensure borrowing is limited in scope.
-            static $name: RefCell<Lexicon> = RefCell::new(Lexicon::default())
+            static $name: ::std::cell::RefCell<$crate::Lexicon> =
+                ::std::cell::RefCell::new($crate::Lexicon::default())
+        }
+
+        /// Runs `f` with a mutable borrow of the thread local `Lexicon`,
+        /// allowing multiple operations to be performed within a single
+        /// borrow. The other utility functions for the thread local
+        /// `Lexicon` must not be called from within `f`, as they would
+        /// attempt to borrow it a second time.
+        pub fn with_lexicon<R>(f: impl FnOnce(&mut $crate::Lexicon) -> R) -> R {
+            $name.with(|lexicon| f(&mut lexicon.borrow_mut()))
+        }
+
+        /// Takes the thread local `Lexicon`, leaving an empty one in its place.
+        pub fn take_lexicon() -> $crate::Lexicon {
+            $name.with(|lexicon| lexicon.take())
+        }
+
+        /// Replaces the thread local `Lexicon` with the given one, returning
+        /// the previous one. Any `Symbol`s interned before the replacement
+        /// remain valid for the returned `Lexicon` only.
+        pub fn replace_lexicon(lexicon: $crate::Lexicon) -> $crate::Lexicon {
+            $name.with(|lex| lex.replace(lexicon))
         }
 
         /// Intern a string slice with the thread local `LEXICON` interner.
         /// Returns the corresponding `Symbol` key.
         /// Equivalent to calling `intern` on `&mut Lexicon`.
-        pub fn intern_once(string: &str) -> Symbol {
+        pub fn intern_once(string: &str) -> $crate::Symbol {
             $name.with(|lexicon| lexicon.borrow_mut().intern(string))
         }
 
-        pub fn intern_many<'t>(strings: &'t [&'t str]) -> Vec<(&'t str, Symbol)> {
+        pub fn intern_many<'t>(strings: &'t [&'t str]) -> Vec<(&'t str, $crate::Symbol)> {
             $name.with(|lexicon| {
                 let mut lex = lexicon.borrow_mut();
                 strings
@@ -260,7 +924,7 @@ macro_rules! local_lexicon {
         /// with the same lifetime (as references must live beyond the
         /// immutable borrow, which they cannot), the resulting `&str` is
         /// converted into an owned `String` and returned.
-        pub fn lookup_once(sym: impl Symbolic) -> String {
+        pub fn lookup_once(sym: impl $crate::Symbolic) -> String {
             $name.with(|lexicon| lexicon.borrow().lookup(sym.get_symbol()).into())
         }
 
@@ -272,7 +936,7 @@ macro_rules! local_lexicon {
         /// immutable borrowing of `Lexicon`, while repeated calls to
         /// `lookup_once` would repeatedly borrow `Lexicon` immutably
         /// multiple times within separate scopes.
-        pub fn lookup_many<S: Symbolic>(syms: &[S]) -> Vec<(Symbol, String)> {
+        pub fn lookup_many<S: $crate::Symbolic>(syms: &[S]) -> Vec<($crate::Symbol, String)> {
             $name.with(|lexicon| {
                 let lex = lexicon.borrow();
                 syms.iter()
@@ -289,24 +953,316 @@ mod test {
     use super::*;
 
     #[test]
-    fn test_intern_once() {
-        let word = "hello";
-        let sym = intern_once(word);
-        println!("{} : {}", word, sym);
+    fn test_symbolic_wrappers() {
+        fn symbol_of(node: impl Symbolic) -> Symbol {
+            node.get_symbol()
+        }
+
+        let sym = Symbol::new(7);
+        let boxed: Box<dyn Symbolic> = Box::new(sym);
+        assert_eq!(symbol_of(&boxed), sym);
+        assert_eq!(symbol_of(Rc::new(sym)), sym);
+        assert_eq!(symbol_of(Arc::new(sym)), sym);
+    }
+
+    #[test]
+    fn test_intern_lookup() {
+        let mut lexicon = Lexicon::default();
+        let words = ["alpha", "beta", "", "alpha"];
+        let syms = words.map(|w| lexicon.intern(w));
+        assert_eq!(syms[0], syms[3]);
+        for (sym, word) in syms.iter().zip(words) {
+            assert_eq!(&lexicon[*sym], word);
+        }
+    }
+
+    #[test]
+    fn test_get() {
+        let mut lexicon = Lexicon::default();
+        let sym = lexicon.intern("present");
+        assert_eq!(lexicon.get("present"), Some(sym));
+        assert_eq!(lexicon.get("absent"), None);
+        assert_eq!(lexicon.intern("other").as_usize(), 1);
     }
 
     #[test]
-    fn test_parallel() {
-        use std::thread;
+    fn test_try_lookup() {
+        let mut lexicon = Lexicon::default();
+        let sym = lexicon.intern("valid");
+        assert_eq!(lexicon.try_lookup(sym), Some("valid"));
+        assert_eq!(lexicon.try_lookup(Symbol::new(1)), None);
+        assert_eq!(lexicon.try_lookup(Symbol::new(u32::MAX - 1)), None);
+    }
 
-        let hi = "hi";
-        let bye = "bye";
+    #[test]
+    fn test_option_niche() {
+        use core::mem::size_of;
+        assert_eq!(size_of::<Option<Symbol>>(), size_of::<Symbol>());
+        assert_eq!(Symbol::try_new(u32::MAX), None);
+        assert_eq!(Symbol::new(7).as_u32(), 7);
+    }
 
-        let syms_1 = intern_many([hi, bye]);
-        let syms_2 = thread::spawn(move || intern_many(["hi", "bye"]))
-            .join()
-            .unwrap();
+    #[test]
+    fn test_validate() {
+        let mut lexicon = Lexicon::with_capacity(1);
+        let syms = (0..100)
+            .map(|i| lexicon.intern(&i.to_string()))
+            .collect::<Vec<_>>();
+        lexicon.validate();
+        assert!(syms.iter().all(|&sym| lexicon.contains_sym(sym)));
+        assert!(!lexicon.contains_sym(Symbol::new(100)));
 
-        assert_eq!(syms_1, syms_2)
+        let reserved = lexicon.reserve_symbol();
+        assert!(!lexicon.contains_sym(reserved));
+        lexicon.define(reserved, "defined");
+        assert!(lexicon.contains_sym(reserved));
+    }
+
+    #[test]
+    fn test_clone_independent() {
+        let mut original = Lexicon::with_capacity(64);
+        let syms = ["a", "b"].map(|s| original.intern(s));
+        let checkpoint = original.checkpoint();
+        original.intern("c");
+        let clone = original.clone();
+
+        original.truncate_to(checkpoint);
+        original.intern("overwrites c");
+        assert_eq!(clone.lookup(Symbol::new(2)), "c");
+        original.clear();
+        original.intern("overwrites a");
+        drop(original);
+
+        assert_eq!(syms.map(|sym| clone.lookup(sym)), ["a", "b"]);
+        clone.validate();
+    }
+
+    #[test]
+    fn test_send_to_worker() {
+        let mut lexicon = Lexicon::default();
+        let main = lexicon.intern("main");
+        let (lexicon, worker) = std::thread::spawn(move || {
+            let worker = lexicon.intern("worker");
+            (lexicon, worker)
+        })
+        .join()
+        .unwrap();
+        assert_eq!(lexicon.lookup(main), "main");
+        assert_eq!(lexicon.lookup(worker), "worker");
+    }
+
+    #[test]
+    fn test_fork() {
+        let mut lexicon = Lexicon::with_capacity(64);
+        let base = lexicon.intern("base");
+        let mut fork = lexicon.fork();
+        assert_eq!(fork.lookup(base), "base");
+
+        let ours = lexicon.intern("ours");
+        let theirs = fork.intern("theirs");
+        assert_eq!(ours, theirs);
+        assert_eq!(lexicon.lookup(ours), "ours");
+        assert_eq!(fork.lookup(theirs), "theirs");
+
+        drop(lexicon);
+        assert_eq!(fork.lookup(base), "base");
+        fork.validate();
+    }
+
+    #[test]
+    fn test_with_hasher() {
+        use core::hash::BuildHasherDefault;
+        use std::collections::hash_map::DefaultHasher;
+
+        let mut lexicon = Lexicon::with_hasher(BuildHasherDefault::<DefaultHasher>::default());
+        let sym = lexicon.intern("hashed");
+        assert_eq!(lexicon.get("hashed"), Some(sym));
+        assert_eq!(lexicon.lookup(sym), "hashed");
+        lexicon.validate();
+    }
+
+    #[test]
+    fn test_intern_hashes_once() {
+        use core::cell::Cell;
+        use std::collections::hash_map::{DefaultHasher, RandomState};
+        use std::rc::Rc;
+
+        #[derive(Default)]
+        struct Counting(Rc<Cell<usize>>, RandomState);
+
+        impl BuildHasher for Counting {
+            type Hasher = DefaultHasher;
+
+            fn build_hasher(&self) -> DefaultHasher {
+                self.0.set(self.0.get() + 1);
+                self.1.build_hasher()
+            }
+        }
+
+        let hasher = Counting::default();
+        let count = Rc::clone(&hasher.0);
+        let mut lexicon = Lexicon::with_hasher(hasher);
+        lexicon.intern("once");
+        assert_eq!(count.get(), 1);
+        lexicon.intern("once");
+        assert_eq!(count.get(), 2);
+    }
+
+    #[test]
+    fn test_intern_static() {
+        static KEYWORDS: &str = "fn let match";
+
+        let mut lexicon = Lexicon::default();
+        let copied = lexicon.intern("let");
+        let syms = KEYWORDS.split(' ').map(|kw| lexicon.intern_static(kw));
+        let syms = syms.collect::<Vec<_>>();
+        assert_eq!(syms[1], copied);
+        assert_eq!(lexicon.lookup(syms[0]).as_ptr(), KEYWORDS.as_ptr());
+        assert_eq!(lexicon.lookup(syms[2]), "match");
+        lexicon.validate();
+    }
+
+    #[test]
+    fn test_intern_owned() {
+        let mut lexicon = Lexicon::default();
+        let name = format!("__tmp{}", 0);
+        let ptr = name.as_ptr();
+        let sym = lexicon.intern_owned(name);
+        assert_eq!(lexicon.lookup(sym).as_ptr(), ptr);
+        assert_eq!(lexicon.intern_owned("__tmp0".to_string()), sym);
+        assert_eq!(lexicon.intern("__tmp0"), sym);
+        lexicon.validate();
+    }
+
+    #[test]
+    fn test_intern_fmt() {
+        let mut lexicon = Lexicon::default();
+        let tmp = (0..3)
+            .map(|n| lexicon.intern_fmt(format_args!("__tmp{}", n)))
+            .collect::<Vec<_>>();
+        assert_eq!(lexicon.lookup(tmp[2]), "__tmp2");
+        assert_eq!(lexicon.intern_display(&"__tmp1"), tmp[1]);
+        assert_eq!(lexicon.intern_display(&42), lexicon.intern("42"));
+        assert_eq!(
+            lexicon.intern_fmt(format_args!("plain")),
+            lexicon.intern("plain")
+        );
+        lexicon.validate();
+    }
+
+    #[test]
+    fn test_get_or_intern_with() {
+        let mut lexicon = Lexicon::default();
+        let mut built = 0;
+        let mut make = |s: &str| {
+            built += 1;
+            Cow::Owned(s.to_lowercase())
+        };
+        let buf = String::from("ident");
+        let sym = lexicon.get_or_intern_with(&buf, || make("IDENT"));
+        assert_eq!(lexicon.get_or_intern_with(&buf, || make("IDENT")), sym);
+        assert_eq!(
+            lexicon.get_or_intern_with("kw", || Cow::Borrowed("kw")),
+            Symbol::new(1)
+        );
+        assert_eq!(built, 1);
+        assert_eq!(lexicon.lookup(sym), "ident");
+    }
+
+    #[test]
+    fn test_concat_symbols() {
+        let mut lexicon = Lexicon::default();
+        let parts = ["foo", "bar"].map(|s| lexicon.intern(s));
+        let concat = lexicon.concat_symbols(&parts);
+        assert_eq!(lexicon.lookup(concat), "foobar");
+        let path = lexicon.concat_symbols_with(&[parts[0], concat], "::");
+        assert_eq!(lexicon.lookup(path), "foo::foobar");
+        assert_eq!(lexicon.concat_symbols(&parts[..1]), parts[0]);
+    }
+
+    #[test]
+    fn test_intern_all() {
+        let mut lexicon = Lexicon::default();
+        let syms = lexicon.intern_all("let x = x + 1".split(' '));
+        assert_eq!(syms.len(), 6);
+        assert_eq!(syms[1], syms[3]);
+        assert_eq!(lexicon.len(), 5);
+
+        let mut buf = Vec::new();
+        for batch in [["a", "b"], ["b", "x"]] {
+            buf.clear();
+            lexicon.intern_all_into(batch, &mut buf);
+        }
+        assert_eq!(buf, [lexicon.intern("b"), syms[1]]);
+    }
+
+    #[test]
+    fn test_intern_path() {
+        let mut lexicon = Lexicon::default();
+        let mut path = Vec::new();
+        lexicon.intern_path("std::vec::Vec", "::", &mut path);
+        assert_eq!(lexicon.join(&path, "/"), "std/vec/Vec");
+
+        let mut ids = Vec::with_capacity(2);
+        lexicon.intern_path("vec", "::", &mut ids);
+        assert_eq!(ids, [path[1]]);
+        lexicon.intern_path("::a", "::", &mut ids);
+        assert_eq!(ids[1..], [lexicon.intern(""), lexicon.intern("a")]);
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut lexicon = Lexicon::with_capacity(4);
+        for i in 0..64 {
+            lexicon.intern(&i.to_string());
+        }
+        let capacity = lexicon.capacity();
+        lexicon.clear();
+
+        assert!(lexicon.is_empty());
+        assert_eq!(lexicon.get("0"), None);
+        assert_eq!(lexicon.capacity(), capacity);
+        assert_eq!(lexicon.intern("reused"), Symbol::new(0));
+        lexicon.validate();
+    }
+
+    #[test]
+    fn test_reserve() {
+        let lexicon = Lexicon::with_capacity(800);
+        assert!(lexicon.memory_usage().vec >= 100 * core::mem::size_of::<arena::Span>());
+
+        let mut lexicon = Lexicon::with_capacity(0);
+        lexicon.reserve(1000, 10_000);
+        let before = lexicon.memory_usage();
+        for i in 0..1000 {
+            lexicon.intern(&format!("{:010}", i));
+        }
+        let after = lexicon.memory_usage();
+        assert_eq!((after.map, after.vec), (before.map, before.vec));
+        assert_eq!(after.active_buffer, 10_000);
+        lexicon.validate();
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_symbolic() {
+        #[derive(Symbolic)]
+        struct Ident {
+            name: Symbol,
+            _span: (u32, u32),
+        }
+
+        #[derive(Symbolic)]
+        struct Path(Vec<Symbol>, #[sym] Ident);
+
+        let mut lexicon = Lexicon::default();
+        let name = lexicon.intern("x");
+        let ident = Ident {
+            name,
+            _span: (0, 1),
+        };
+        assert_eq!(ident.get_symbol(), name);
+        let path = Path(alloc::vec![name, name], ident);
+        assert_eq!((path.0.len(), path.get_symbol()), (2, name));
     }
 }
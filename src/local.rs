@@ -0,0 +1,31 @@
+//! A thread local interner, for single-threaded tools (such as REPLs) that
+//! want implicit access to a `Lexicon` without going through a global lock.
+//! Every thread gets its own `Lexicon`, so `Symbol`s interned here must not
+//! be sent to and resolved on other threads.
+
+crate::local_lexicon!(LEXICON);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_with_lexicon() {
+        let sym = intern_once("local");
+        let (again, len) = with_lexicon(|lex| (lex.intern("local"), lex.lookup(sym).len()));
+        assert_eq!(sym, again);
+        assert_eq!(len, 5);
+    }
+
+    #[test]
+    fn test_take_and_replace() {
+        let sym = intern_once("taken");
+        let taken = take_lexicon();
+        assert_eq!(taken.lookup(sym), "taken");
+
+        let fresh = intern_once("fresh");
+        let previous = replace_lexicon(taken);
+        assert_eq!(previous.lookup(fresh), "fresh");
+        assert_eq!(lookup_once(sym), "taken");
+    }
+}
@@ -0,0 +1,221 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use hashbrown::{DefaultHashBuilder, HashTable};
+
+/// Key of an `LruLexicon`. Since the slots of evicted strings are recycled,
+/// each key also records the generation of its slot, so that keys of
+/// evicted strings never resolve to the strings replacing them.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct LruSymbol {
+    idx: u32,
+    generation: u32,
+}
+
+/// String interner holding at most a fixed number of strings, evicting the
+/// least recently used one to make room for a new one, e.g., for caching
+/// header names in a proxy, where the set of strings is unbounded.
+///
+/// Interning a string (or `touch`ing its symbol) marks it as used. The
+/// symbols of evicted strings are invalidated: resolving them returns
+/// `None`, even once their slot holds another string.
+#[derive(Clone, Debug)]
+pub struct LruLexicon {
+    map: HashTable<u32>,
+    hasher: DefaultHashBuilder,
+    slots: Vec<Slot>,
+    /// Most and least recently used slots, or `NONE` if empty.
+    head: u32,
+    tail: u32,
+    capacity: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Slot {
+    string: Box<str>,
+    generation: u32,
+    /// Neighbouring slots in order of use, more recent first.
+    prev: u32,
+    next: u32,
+}
+
+const NONE: u32 = u32::MAX;
+
+impl LruLexicon {
+    /// Creates a new `LruLexicon` holding at most `capacity` strings.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero, or does not fit into a `u32`.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity of `LruLexicon` must not be zero");
+        assert!(
+            capacity < NONE as usize,
+            "capacity of `LruLexicon` is too large"
+        );
+        Self {
+            map: HashTable::with_capacity(capacity),
+            hasher: DefaultHashBuilder::default(),
+            slots: Vec::new(),
+            head: NONE,
+            tail: NONE,
+            capacity,
+        }
+    }
+
+    /// Interns `string`, marking it as the most recently used string. If it
+    /// is new while this `LruLexicon` is full, the least recently used
+    /// string is evicted first.
+    pub fn intern(&mut self, string: &str) -> LruSymbol {
+        let hash = self.hasher.hash_one(string);
+        if let Some(idx) = self.find(hash, string) {
+            self.move_to_front(idx);
+            return self.symbol(idx);
+        }
+
+        let idx = if self.slots.len() < self.capacity {
+            self.slots.push(Slot {
+                string: string.into(),
+                generation: 0,
+                prev: NONE,
+                next: NONE,
+            });
+            (self.slots.len() - 1) as u32
+        } else {
+            let idx = self.tail;
+            self.unlink(idx);
+            let (slots, hasher) = (&self.slots, &self.hasher);
+            let evicted = hasher.hash_one(&*slots[idx as usize].string);
+            if let Ok(entry) = self.map.find_entry(evicted, |&other| other == idx) {
+                entry.remove();
+            }
+            let slot = &mut self.slots[idx as usize];
+            slot.string = string.into();
+            slot.generation = slot.generation.wrapping_add(1);
+            idx
+        };
+        self.push_front(idx);
+        let (slots, hasher) = (&self.slots, &self.hasher);
+        self.map.insert_unique(hash, idx, |&idx| {
+            hasher.hash_one(&*slots[idx as usize].string)
+        });
+        self.symbol(idx)
+    }
+
+    /// Returns the symbol of `string` if it is currently interned, without
+    /// marking it as used.
+    pub fn get(&self, string: &str) -> Option<LruSymbol> {
+        let idx = self.find(self.hasher.hash_one(string), string)?;
+        Some(self.symbol(idx))
+    }
+
+    fn find(&self, hash: u64, string: &str) -> Option<u32> {
+        let slots = &self.slots;
+        self.map
+            .find(hash, |&idx| &*slots[idx as usize].string == string)
+            .copied()
+    }
+
+    /// Marks the string of `id` as the most recently used one, returning
+    /// `false` if it was already evicted.
+    pub fn touch(&mut self, id: LruSymbol) -> bool {
+        let live = self.try_lookup(id).is_some();
+        if live {
+            self.move_to_front(id.idx);
+        }
+        live
+    }
+
+    /// # Panics
+    /// Panics if the string of `id` was evicted, see `try_lookup`.
+    pub fn lookup(&self, id: LruSymbol) -> &str {
+        match self.try_lookup(id) {
+            Some(string) => string,
+            None => panic!("symbol `{:?}` was evicted", id),
+        }
+    }
+
+    /// Returns the string of `id` (without marking it as used), or `None` if
+    /// it was evicted.
+    pub fn try_lookup(&self, id: LruSymbol) -> Option<&str> {
+        let slot = self.slots.get(id.idx as usize)?;
+        (slot.generation == id.generation).then_some(&*slot.string)
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn symbol(&self, idx: u32) -> LruSymbol {
+        LruSymbol {
+            idx,
+            generation: self.slots[idx as usize].generation,
+        }
+    }
+
+    fn move_to_front(&mut self, idx: u32) {
+        if self.head != idx {
+            self.unlink(idx);
+            self.push_front(idx);
+        }
+    }
+
+    fn unlink(&mut self, idx: u32) {
+        let Slot { prev, next, .. } = self.slots[idx as usize];
+        match prev {
+            NONE => self.head = next,
+            prev => self.slots[prev as usize].next = next,
+        }
+        match next {
+            NONE => self.tail = prev,
+            next => self.slots[next as usize].prev = prev,
+        }
+    }
+
+    fn push_front(&mut self, idx: u32) {
+        let slot = &mut self.slots[idx as usize];
+        slot.prev = NONE;
+        slot.next = self.head;
+        match self.head {
+            NONE => self.tail = idx,
+            head => self.slots[head as usize].prev = idx,
+        }
+        self.head = idx;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_eviction() {
+        let mut lexicon = LruLexicon::new(2);
+        let a = lexicon.intern("a");
+        let b = lexicon.intern("b");
+        assert_eq!(lexicon.intern("a"), a);
+
+        // "b" is the least recently used
+        let c = lexicon.intern("c");
+        assert_eq!(lexicon.try_lookup(b), None);
+        assert_eq!(lexicon.get("b"), None);
+        assert_eq!(lexicon.lookup(a), "a");
+        assert_eq!(lexicon.lookup(c), "c");
+        assert_eq!(lexicon.len(), 2);
+
+        assert!(lexicon.touch(a));
+        assert!(!lexicon.touch(b));
+        let b = lexicon.intern("b");
+        assert_eq!(lexicon.try_lookup(c), None);
+        assert_eq!(lexicon.lookup(b), "b");
+        assert_eq!(lexicon.get("a"), Some(a));
+    }
+}
@@ -0,0 +1,246 @@
+use std::io;
+use std::str;
+
+use crate::binary::{invalid_data, read_header, Crc32};
+use crate::{Resolver, Symbol, Symbolic};
+
+/// Read-only lexicon resolving symbols directly out of the binary encoding
+/// produced by `Lexicon::write_to`, without copying any strings into memory
+/// of its own. The encoding may be held in any byte buffer, though for very
+/// large dictionaries it is best memory-mapped (see `MappedLexicon::open`,
+/// available with the `mmap` feature).
+///
+/// Only the header and the offsets table are validated upon construction;
+/// each string is checked to be valid UTF-8 only once it is resolved, so
+/// that opening a dictionary never requires touching all of its bytes. Its
+/// checksum is thus only verified through `verify`.
+#[derive(Clone, Debug)]
+pub struct MappedLexicon<B> {
+    bytes: B,
+    /// Position of the offsets table, i.e., length of the header.
+    offsets: usize,
+    count: usize,
+    checksum: Option<u32>,
+    /// Number of reserved symbols, listed right after the offsets table.
+    reserved: usize,
+}
+
+impl<B: AsRef<[u8]>> MappedLexicon<B> {
+    pub fn new(bytes: B) -> io::Result<Self> {
+        let buf = bytes.as_ref();
+        let header = read_header(&mut &buf[..])?;
+        let (offsets, count) = (header.len, header.count);
+        let data_start = count
+            .checked_add(1 + header.reserved)
+            .and_then(|n| n.checked_mul(4))
+            .and_then(|n| n.checked_add(offsets))
+            .filter(|&n| n <= buf.len())
+            .ok_or(io::ErrorKind::UnexpectedEof)?;
+        let mut last = 0;
+        for i in 0..=count {
+            let offset = read_u32(buf, offsets + 4 * i);
+            if (i == 0 && offset != 0) || offset < last {
+                return Err(invalid_data("lexicon offsets are not ascending"));
+            }
+            last = offset;
+        }
+        let reserved = offsets + 4 * (count + 1);
+        let mut prev = None;
+        for i in 0..header.reserved {
+            let id = read_u32(buf, reserved + 4 * i);
+            if prev.is_some_and(|prev| id <= prev) || id as usize >= count {
+                return Err(invalid_data("lexicon reserved symbols are not ascending"));
+            }
+            prev = Some(id);
+        }
+        if data_start + last as usize > buf.len() {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        Ok(Self {
+            bytes,
+            offsets,
+            count,
+            checksum: header.checksum,
+            reserved: header.reserved,
+        })
+    }
+
+    /// Verifies the checksum of the encoding, reading every string once.
+    /// Encodings of version `1` carry no checksum, and always pass.
+    pub fn verify(&self) -> io::Result<()> {
+        let buf = self.bytes.as_ref();
+        let data = self.data();
+        let end = data + read_u32(buf, self.offsets + 4 * self.count) as usize;
+        let mut crc = Crc32::new();
+        crc.update(&buf[self.offsets..end]);
+        match self.checksum {
+            Some(checksum) if checksum != crc.finish() => {
+                Err(invalid_data("lexicon checksum mismatch"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn lookup(&self, id: Symbol) -> &str {
+        assert!(
+            !self.is_reserved(id),
+            "symbol `{}` is reserved",
+            id.as_u32()
+        );
+        let bytes = self.bytes_of(id);
+        match str::from_utf8(bytes) {
+            Ok(string) => string,
+            Err(e) => panic!("symbol `{}` is not valid UTF-8: {}", id.as_u32(), e),
+        }
+    }
+
+    /// Returns the string corresponding to `id`, or `None` if `id` is out of
+    /// bounds, reserved (see `is_reserved`) or its bytes are not valid UTF-8.
+    pub fn try_lookup(&self, id: Symbol) -> Option<&str> {
+        if id.as_usize() >= self.count || self.is_reserved(id) {
+            return None;
+        }
+        str::from_utf8(self.bytes_of(id)).ok()
+    }
+
+    /// Whether `id` was reserved but not defined in the encoded `Lexicon`
+    /// (see `Lexicon::reserve_symbol`), in which case it has no string.
+    pub fn is_reserved(&self, id: Symbol) -> bool {
+        let buf = self.bytes.as_ref();
+        let reserved = self.offsets + 4 * (self.count + 1);
+        let ids = (0..self.reserved).map(|i| read_u32(buf, reserved + 4 * i));
+        // the table is sorted, but usually tiny
+        ids.take_while(|&other| other <= id.as_u32())
+            .any(|other| other == id.as_u32())
+    }
+
+    /// Position of the string bytes, following the offsets and reserved
+    /// symbols.
+    fn data(&self) -> usize {
+        self.offsets + 4 * (self.count + 1 + self.reserved)
+    }
+
+    fn bytes_of(&self, id: Symbol) -> &[u8] {
+        let buf = self.bytes.as_ref();
+        let i = id.as_usize();
+        assert!(i < self.count, "symbol `{}` was not interned", id.as_u32());
+
+        let data = self.data();
+        let start = data + read_u32(buf, self.offsets + 4 * i) as usize;
+        let end = data + read_u32(buf, self.offsets + 4 * (i + 1)) as usize;
+        &buf[start..end]
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Returns the underlying byte buffer.
+    pub fn into_inner(self) -> B {
+        self.bytes
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl MappedLexicon<memmap2::Mmap> {
+    /// Memory-maps the encoded lexicon at `path`.
+    ///
+    /// The file must not be modified for as long as the returned
+    /// `MappedLexicon` is alive, as doing so would change the strings out
+    /// from under any references previously resolved from it.
+    pub fn open(path: impl AsRef<std::path::Path>) -> io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        // see above regarding modifications to the file
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        Self::new(map)
+    }
+}
+
+impl<B: AsRef<[u8]>, S: Symbolic> std::ops::Index<S> for MappedLexicon<B> {
+    type Output = str;
+
+    fn index(&self, index: S) -> &Self::Output {
+        self.lookup(index.get_symbol())
+    }
+}
+
+impl<B: AsRef<[u8]>> Resolver for MappedLexicon<B> {
+    fn resolve(&self, id: Symbol) -> &str {
+        self.lookup(id)
+    }
+
+    fn try_resolve(&self, id: Symbol) -> Option<&str> {
+        self.try_lookup(id)
+    }
+}
+
+fn read_u32(buf: &[u8], at: usize) -> u32 {
+    let mut bytes = [0; 4];
+    bytes.copy_from_slice(&buf[at..at + 4]);
+    u32::from_le_bytes(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Lexicon;
+
+    #[test]
+    fn test_mapped_lookup() {
+        let mut lexicon = Lexicon::default();
+        let syms = ["mapped", "", "lexicon"].map(|s| lexicon.intern(s));
+        let mut bytes = Vec::new();
+        lexicon.write_to(&mut bytes).unwrap();
+
+        let mapped = MappedLexicon::new(&bytes[..]).unwrap();
+        assert_eq!(mapped.len(), 3);
+        for sym in syms {
+            assert_eq!(&mapped[sym], lexicon.lookup(sym));
+        }
+
+        assert_eq!(mapped.try_lookup(Symbol::new(3)), None);
+        assert!(!mapped.is_reserved(syms[1]));
+        assert!(mapped.verify().is_ok());
+        assert!(MappedLexicon::new(&bytes[..bytes.len() - 1]).is_err());
+
+        let last = bytes.len() - 1;
+        bytes[last] = b'N';
+        assert!(MappedLexicon::new(&bytes[..]).unwrap().verify().is_err());
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_open() {
+        let mut lexicon = Lexicon::default();
+        let sym = lexicon.intern("on disk");
+        let path = std::env::temp_dir().join(format!("lexicon-{}.lxcn", std::process::id()));
+        lexicon
+            .write_to(std::fs::File::create(&path).unwrap())
+            .unwrap();
+
+        let mapped = MappedLexicon::open(&path).unwrap();
+        assert_eq!(mapped.lookup(sym), "on disk");
+        drop(mapped);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_mapped_reserved() {
+        let mut lexicon = Lexicon::default();
+        let reserved = lexicon.reserve_symbol();
+        let sym = lexicon.intern("defined");
+        let mut bytes = Vec::new();
+        lexicon.write_to(&mut bytes).unwrap();
+
+        let mapped = MappedLexicon::new(&bytes[..]).unwrap();
+        assert!(mapped.is_reserved(reserved));
+        assert_eq!(mapped.try_lookup(reserved), None);
+        assert_eq!(mapped.lookup(sym), "defined");
+        assert!(mapped.verify().is_ok());
+    }
+}
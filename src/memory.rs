@@ -0,0 +1,208 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+use core::mem::size_of;
+
+use crate::{Backend, Checkpoint, Key, Lexicon, LexiconError};
+
+/// Breakdown of the heap memory held by a `Lexicon`, in bytes. Created by
+/// `Lexicon::memory_usage`.
+///
+/// Buffers shared with clones of the `Lexicon` are counted in full by each
+/// of them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Capacity of the buffer strings are currently stored into.
+    pub active_buffer: usize,
+    /// Capacity of every other buffer of the backend, i.e., those already
+    /// filled up, and the owned strings stored without being copied.
+    pub retired_buffers: usize,
+    /// Size of the table mapping strings to their keys.
+    pub map: usize,
+    /// Size of the table mapping keys to their strings.
+    pub vec: usize,
+    /// Size of every other table: aliases, reserved keys, open scopes, the
+    /// prefix index (if maintained), the sorted index (if built) and the
+    /// formatting buffer.
+    pub auxiliary: usize,
+}
+
+impl MemoryStats {
+    pub fn total(&self) -> usize {
+        self.active_buffer + self.retired_buffers + self.map + self.vec + self.auxiliary
+    }
+}
+
+impl<K: Key, H: BuildHasher, B: Backend> Lexicon<K, H, B> {
+    /// Returns how much heap memory this `Lexicon` holds, and what for, e.g.,
+    /// to account for it in memory statistics.
+    pub fn memory_usage(&self) -> MemoryStats {
+        let (active_buffer, retired_buffers) = self.backend.allocated();
+        let auxiliary = self.aliases.capacity() * size_of::<(B::Span, K)>()
+            + self.alias_map.allocation_size()
+            + self.pending.allocation_size()
+            + self.defined.allocation_size()
+            + self.scopes.capacity() * size_of::<Checkpoint<B::Mark>>()
+            + self
+                .prefixes
+                .as_ref()
+                .map_or(0, |trie| trie.allocation_size())
+            + self.scratch.capacity()
+            + self.sorted.allocation_size();
+        MemoryStats {
+            active_buffer,
+            retired_buffers,
+            map: self.map.allocation_size(),
+            vec: self.vec.capacity() * size_of::<B::Span>(),
+            auxiliary,
+        }
+    }
+
+    /// Limits the total length of the strings stored in this `Lexicon` to
+    /// `limit` bytes (or lifts the limit, if `None`), e.g., when interning
+    /// untrusted input. Interning a new string which would exceed it fails
+    /// with `LexiconError::MemoryLimitExceeded` instead, leaving the
+    /// `Lexicon` untouched. Aliases count against the limit as well.
+    ///
+    /// Only the strings themselves are counted, not the tables indexing
+    /// them, whose size is bounded by the number of distinct strings (and
+    /// thus by their total length) all the same. Strings already stored
+    /// are kept, even if they exceed a newly lowered limit.
+    pub fn set_memory_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+    }
+
+    pub fn memory_limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Returns the total length of the strings stored in this `Lexicon`, as
+    /// counted against the memory limit.
+    pub fn stored_bytes(&self) -> usize {
+        self.stored
+    }
+
+    /// Counts a new string of `len` bytes against the memory limit, failing
+    /// if it does not fit.
+    pub(crate) fn charge(&mut self, len: usize) -> Result<(), LexiconError> {
+        let stored = self.stored.saturating_add(len);
+        if self.limit.is_some_and(|limit| stored > limit) {
+            return Err(LexiconError::MemoryLimitExceeded);
+        }
+        self.stored = stored;
+        Ok(())
+    }
+
+    /// Releases the memory this `Lexicon` holds beyond what its strings
+    /// need, e.g., once done interning. The strings are moved into storage
+    /// allocated to fit (see `Backend::shrink_to_fit`), and every table is
+    /// shrunk to its length. Every checkpoint (and open scope) is
+    /// invalidated.
+    pub fn shrink_to_fit(&mut self) {
+        let aliases = self.aliases.iter_mut().map(|(span, _)| span);
+        self.backend
+            .shrink_to_fit(self.vec.iter_mut().chain(aliases));
+
+        let (vec, aliases, backend, hasher) =
+            (&self.vec, &self.aliases, &self.backend, &self.hasher);
+        self.map
+            .shrink_to_fit(|id| hasher.hash_one(backend.get(vec[id.to_usize()])));
+        self.alias_map
+            .shrink_to_fit(|&idx| hasher.hash_one(backend.get(aliases[idx].0)));
+        self.vec.shrink_to_fit();
+        self.aliases.shrink_to_fit();
+        self.pending.shrink_to_fit();
+        self.defined.shrink_to_fit();
+        self.scopes = Vec::new();
+        self.scratch = String::new();
+        if let Some(trie) = &mut self.prefixes {
+            trie.shrink_to_fit();
+        }
+        self.side_tables.shrink_to_fit();
+        self.frequencies.shrink_to_fit();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::arena::Arena;
+    use crate::backend::BufferBackend;
+    use crate::Symbol;
+
+    #[test]
+    fn test_memory_usage() {
+        let mut lexicon = Lexicon::with_capacity(16);
+        let empty = lexicon.memory_usage();
+        assert_eq!(empty.active_buffer, 16);
+        assert_eq!(empty.retired_buffers, 0);
+
+        lexicon.intern("short");
+        lexicon.intern(&"x".repeat(Arena::CHUNK_SIZE));
+        lexicon.intern_owned(String::with_capacity(100) + "owned");
+        let stats = lexicon.memory_usage();
+        assert_eq!(stats.active_buffer, Arena::CHUNK_SIZE);
+        assert_eq!(stats.retired_buffers, 16 + 100);
+        assert!(stats.map > 0 && stats.vec >= 3 * size_of::<crate::arena::Span>());
+        assert!(stats.total() > stats.active_buffer + stats.retired_buffers);
+    }
+
+    #[test]
+    fn test_shrink_to_fit() {
+        let mut lexicon = Lexicon::<Symbol>::default();
+        let strings = (0..1000)
+            .map(|i| alloc::format!("string{}", i))
+            .collect::<Vec<_>>();
+        let ids = lexicon.intern_all(&strings);
+        lexicon.alias(ids[0], "first");
+        let len = strings.iter().map(|s| s.len()).sum::<usize>() + "first".len();
+
+        lexicon.shrink_to_fit();
+        let stats = lexicon.memory_usage();
+        assert_eq!(stats.active_buffer + stats.retired_buffers, len);
+        assert_eq!(stats.vec, 1000 * size_of::<crate::arena::Span>());
+        for (id, string) in ids.iter().zip(&strings) {
+            assert_eq!(lexicon.lookup(*id), string);
+            assert_eq!(lexicon.get(string), Some(*id));
+        }
+        assert_eq!(lexicon.get("first"), Some(ids[0]));
+        let new = lexicon.intern("new");
+        assert_eq!(lexicon.lookup(new), "new");
+
+        let mut lexicon = Lexicon::<Symbol, _, _>::with_backend(BufferBackend::default());
+        let ids = lexicon.intern_all(&strings);
+        lexicon.shrink_to_fit();
+        assert_eq!(lexicon.memory_usage().active_buffer, len - "first".len());
+        assert_eq!(lexicon.lookup(ids[999]), "string999");
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        let mut lexicon = Lexicon::<Symbol>::default();
+        lexicon.set_memory_limit(Some(10));
+        let a = lexicon.intern("abcde");
+        assert_eq!(
+            lexicon.try_intern("fghijk"),
+            Err(LexiconError::MemoryLimitExceeded)
+        );
+        assert_eq!(lexicon.len(), 1);
+        assert_eq!(lexicon.try_intern("abcde"), Ok(a));
+        assert_eq!(
+            lexicon.try_alias(a, "bcdefg"),
+            Err(LexiconError::MemoryLimitExceeded)
+        );
+
+        let checkpoint = lexicon.checkpoint();
+        lexicon.intern("fghij");
+        assert_eq!(lexicon.stored_bytes(), 10);
+        lexicon.truncate_to(checkpoint);
+        assert_eq!(lexicon.stored_bytes(), 5);
+        lexicon.alias(a, "ABCDE");
+        assert!(lexicon.try_intern("x").is_err());
+
+        lexicon.set_memory_limit(None);
+        lexicon.intern("fghijk");
+        lexicon.clear();
+        assert_eq!(lexicon.stored_bytes(), 0);
+    }
+}
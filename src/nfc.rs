@@ -0,0 +1,51 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+use crate::{NormalizedLexicon, Normalizer};
+
+/// Normalizes strings to Unicode Normalization Form C, such that
+/// canonically equivalent strings (e.g., `"é"` as a single code point, or as
+/// `e` followed by a combining accent) are interned as the same `Symbol`, as
+/// recommended for identifiers by UAX #15 and #31.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Nfc;
+
+impl Normalizer for Nfc {
+    fn normalize<'a>(&self, string: &'a str) -> Cow<'a, str> {
+        match is_nfc_quick(string.chars()) {
+            IsNormalized::Yes => Cow::Borrowed(string),
+            _ => Cow::Owned(string.nfc().collect::<String>()),
+        }
+    }
+}
+
+/// Interner normalizing every string to NFC (see `Nfc`) before interning it.
+/// Symbols resolve to the normalized form of their string.
+pub type NfcLexicon = NormalizedLexicon<Nfc>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::normalize::Lowercase;
+
+    #[test]
+    fn test_nfc() {
+        let mut lexicon = NfcLexicon::new();
+        let composed = lexicon.intern("caf\u{e9}");
+        assert_eq!(lexicon.intern("cafe\u{301}"), composed);
+        assert_eq!(lexicon.get("cafe\u{301}"), Some(composed));
+        assert_eq!(lexicon.lookup(composed), "caf\u{e9}");
+
+        let decomposed = lexicon.intern("\u{212b}ngstr\u{f6}m");
+        assert_eq!(lexicon.lookup(decomposed), "\u{c5}ngstr\u{f6}m");
+        assert_eq!(lexicon.len(), 2);
+    }
+
+    #[test]
+    fn test_nfc_lowercase() {
+        let mut lexicon = NormalizedLexicon::<(Nfc, Lowercase)>::new();
+        assert_eq!(lexicon.intern("CAFE\u{301}"), lexicon.intern("caf\u{e9}"));
+    }
+}
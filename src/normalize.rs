@@ -0,0 +1,227 @@
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use core::fmt;
+
+use crate::caseless::fold;
+use crate::{Interner, Lexicon, LexiconError, Resolver, Symbol, Symbolic};
+
+/// Transformation applied by a `NormalizedLexicon` to every string before
+/// interning (or looking up) it, such that all strings normalizing to the
+/// same form share a single `Symbol`.
+///
+/// Normalizers may be composed through tuples: `(Trim, Lowercase)` trims a
+/// string, then lowercases the result.
+pub trait Normalizer {
+    /// Returns the normalized form of `string`, which should be borrowed
+    /// whenever `string` is already normalized.
+    fn normalize<'a>(&self, string: &'a str) -> Cow<'a, str>;
+}
+
+/// Strips leading and trailing whitespace.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Trim;
+
+impl Normalizer for Trim {
+    fn normalize<'a>(&self, string: &'a str) -> Cow<'a, str> {
+        Cow::Borrowed(string.trim())
+    }
+}
+
+/// Lowercases strings (as by `str::to_lowercase`). See `CaselessLexicon` to
+/// also keep the original spelling of each string.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Lowercase;
+
+impl Normalizer for Lowercase {
+    fn normalize<'a>(&self, string: &'a str) -> Cow<'a, str> {
+        fold(string)
+    }
+}
+
+/// Maps strings to a canonical form through a user-provided function, such
+/// that semantically equal spellings (e.g., `0x10` and `16`) share a single
+/// `Symbol`, with the canonical form being the one stored. See
+/// `NormalizedLexicon::with_canonicalizer`.
+#[derive(Clone, Copy)]
+pub struct Canonicalizer<F>(pub F);
+
+impl<F: Fn(&str) -> Cow<'_, str>> Normalizer for Canonicalizer<F> {
+    fn normalize<'a>(&self, string: &'a str) -> Cow<'a, str> {
+        (self.0)(string)
+    }
+}
+
+impl<F> fmt::Debug for Canonicalizer<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Canonicalizer").finish_non_exhaustive()
+    }
+}
+
+impl<A: Normalizer, B: Normalizer> Normalizer for (A, B) {
+    fn normalize<'a>(&self, string: &'a str) -> Cow<'a, str> {
+        match self.0.normalize(string) {
+            Cow::Borrowed(string) => self.1.normalize(string),
+            Cow::Owned(string) => match self.1.normalize(&string) {
+                Cow::Borrowed(normalized) if normalized.len() == string.len() => Cow::Owned(string),
+                normalized => Cow::Owned(normalized.to_string()),
+            },
+        }
+    }
+}
+
+impl<A: Normalizer, B: Normalizer, C: Normalizer> Normalizer for (A, B, C) {
+    fn normalize<'a>(&self, string: &'a str) -> Cow<'a, str> {
+        ((&self.0, &self.1), &self.2).normalize(string)
+    }
+}
+
+impl<N: Normalizer + ?Sized> Normalizer for &N {
+    fn normalize<'a>(&self, string: &'a str) -> Cow<'a, str> {
+        (**self).normalize(string)
+    }
+}
+
+/// Interner applying the `Normalizer` `N` to every string before interning
+/// it, e.g., `NormalizedLexicon<(Trim, Lowercase)>`. Symbols resolve to the
+/// normalized form of their string.
+#[derive(Clone, Debug, Default)]
+pub struct NormalizedLexicon<N> {
+    inner: Lexicon,
+    normalizer: N,
+}
+
+impl<N: Normalizer + Default> NormalizedLexicon<N> {
+    pub fn new() -> Self {
+        Self::with_normalizer(N::default())
+    }
+}
+
+impl<F: Fn(&str) -> Cow<'_, str>> NormalizedLexicon<Canonicalizer<F>> {
+    /// Creates a new `NormalizedLexicon` mapping every string to the
+    /// canonical form returned by `canonicalize` before interning it.
+    pub fn with_canonicalizer(canonicalize: F) -> Self {
+        Self::with_normalizer(Canonicalizer(canonicalize))
+    }
+}
+
+impl<N: Normalizer> NormalizedLexicon<N> {
+    pub fn with_normalizer(normalizer: N) -> Self {
+        Self {
+            inner: Lexicon::default(),
+            normalizer,
+        }
+    }
+
+    pub fn intern(&mut self, string: &str) -> Symbol {
+        match self.try_intern(string) {
+            Ok(id) => id,
+            Err(e) => panic!("{} while interning `{}`", e, string),
+        }
+    }
+
+    /// Like `intern`, but returns an error rather than panicking if `string`
+    /// is new and cannot be interned (see `Lexicon::try_intern`).
+    pub fn try_intern(&mut self, string: &str) -> Result<Symbol, LexiconError> {
+        self.inner.try_intern(&self.normalizer.normalize(string))
+    }
+
+    /// Returns the `Symbol` of `string` (in any form normalizing to the same
+    /// string) if it has already been interned.
+    pub fn get(&self, string: &str) -> Option<Symbol> {
+        self.inner.get(&self.normalizer.normalize(string))
+    }
+
+    pub fn lookup(&self, id: Symbol) -> &str {
+        self.inner.lookup(id)
+    }
+
+    pub fn try_lookup(&self, id: Symbol) -> Option<&str> {
+        self.inner.try_lookup(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn normalizer(&self) -> &N {
+        &self.normalizer
+    }
+
+    /// Returns the underlying `Lexicon`, holding the normalized strings.
+    pub fn into_lexicon(self) -> Lexicon {
+        self.inner
+    }
+}
+
+impl<N: Normalizer, S: Symbolic> core::ops::Index<S> for NormalizedLexicon<N> {
+    type Output = str;
+
+    fn index(&self, index: S) -> &Self::Output {
+        self.lookup(index.get_symbol())
+    }
+}
+
+impl<N: Normalizer> Resolver for NormalizedLexicon<N> {
+    fn resolve(&self, id: Symbol) -> &str {
+        self.lookup(id)
+    }
+
+    fn try_resolve(&self, id: Symbol) -> Option<&str> {
+        self.try_lookup(id)
+    }
+}
+
+impl<N: Normalizer> Interner for NormalizedLexicon<N> {
+    type Error = LexiconError;
+
+    fn try_intern(&mut self, string: &str) -> Result<Symbol, LexiconError> {
+        self.try_intern(string)
+    }
+
+    fn get(&self, string: &str) -> Option<Symbol> {
+        self.get(string)
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalizers() {
+        let mut lexicon = NormalizedLexicon::<(Trim, Lowercase)>::new();
+        let sym = lexicon.intern("  Foo ");
+        assert_eq!(lexicon.intern("FOO"), sym);
+        assert_eq!(lexicon.get("foo\n"), Some(sym));
+        assert_eq!(lexicon.lookup(sym), "foo");
+
+        assert!(matches!(
+            (Trim, Lowercase).normalize("a "),
+            Cow::Borrowed("a")
+        ));
+        assert!(matches!((Lowercase, Trim).normalize(" A "), Cow::Owned(s) if s == "a"));
+    }
+
+    #[test]
+    fn test_canonicalizer() {
+        let mut lexicon = NormalizedLexicon::with_canonicalizer(|s: &str| {
+            match s.strip_prefix("0x").map(|hex| u64::from_str_radix(hex, 16)) {
+                Some(Ok(n)) => Cow::Owned(n.to_string()),
+                _ => Cow::Borrowed(s.trim()),
+            }
+        });
+        let sixteen = lexicon.intern("16");
+        assert_eq!(lexicon.intern("0x10"), sixteen);
+        assert_eq!(lexicon.intern("16 "), sixteen);
+        let ff = lexicon.intern("0xff");
+        assert_eq!(lexicon.lookup(ff), "255");
+    }
+}
@@ -0,0 +1,102 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::BuildHasher;
+
+use crate::{Backend, Key, Lexicon};
+
+type Observer<K> = Box<dyn FnMut(K, &str) + Send + Sync>;
+
+/// Callbacks registered through `Lexicon::on_intern`.
+pub(crate) struct Observers<K> {
+    observers: Vec<Observer<K>>,
+}
+
+impl<K: Copy> Observers<K> {
+    pub fn is_empty(&self) -> bool {
+        self.observers.is_empty()
+    }
+
+    pub fn notify(&mut self, id: K, string: &str) {
+        for observer in &mut self.observers {
+            observer(id, string);
+        }
+    }
+}
+
+impl<K> Default for Observers<K> {
+    fn default() -> Self {
+        Self {
+            observers: Vec::new(),
+        }
+    }
+}
+
+/// Observers are not carried over to clones, as closures cannot be cloned.
+impl<K> Clone for Observers<K> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl<K> fmt::Debug for Observers<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Observers({})", self.observers.len())
+    }
+}
+
+impl<K: Key, H: BuildHasher, B: Backend> Lexicon<K, H, B> {
+    /// Registers `observer` to be called with every string interned (or
+    /// defined, see `define`) from now on, along with its key, but only
+    /// when it is new to this `Lexicon`, e.g., to maintain side tables
+    /// indexed by key. Observers are called in the order they were
+    /// registered.
+    ///
+    /// Observers are not carried over to clones of this `Lexicon`, and are
+    /// not called for strings merely re-added by `retain`.
+    pub fn on_intern(&mut self, observer: impl FnMut(K, &str) + Send + Sync + 'static) {
+        self.observers.observers.push(Box::new(observer));
+    }
+
+    /// Unregisters every observer registered through `on_intern`.
+    pub fn clear_observers(&mut self) {
+        self.observers = Observers::default();
+    }
+
+    /// Calls the observers with the newly stored string of `id`.
+    pub(crate) fn notify(&mut self, id: K) {
+        if !self.observers.is_empty() {
+            let string = self.backend.get(self.vec[id.to_usize()]);
+            self.observers.notify(id, string);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Symbol;
+    use alloc::string::String;
+    use alloc::sync::Arc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_on_intern() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut lexicon = Lexicon::<Symbol>::default();
+        lexicon.intern("before");
+        let log = seen.clone();
+        lexicon.on_intern(move |id, string| log.lock().unwrap().push((id, String::from(string))));
+
+        let a = lexicon.intern("a");
+        lexicon.intern("a");
+        lexicon.intern("before");
+        let b = lexicon.reserve_symbol();
+        lexicon.define(b, "b");
+        lexicon.clone().intern("c");
+        assert_eq!(
+            *seen.lock().unwrap(),
+            [(a, String::from("a")), (b, String::from("b"))]
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use core::hash::BuildHasher;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use hashbrown::HashTable;
+use rayon::prelude::*;
+
+use crate::{Backend, Lexicon, Symbol};
+
+/// Number of strings below which `par_from_slice` interns sequentially, as
+/// partitioning them would cost more than it saves.
+const SEQUENTIAL_LEN: usize = 1 << 14;
+
+impl Lexicon {
+    /// Creates a new `Lexicon` holding every string of `strings`, returning
+    /// it along with the `Symbol` of each string (in order). Symbols are
+    /// assigned in order of first occurrence, exactly as if the strings were
+    /// interned one after another.
+    ///
+    /// The strings are hashed and deduplicated in parallel on `rayon`'s
+    /// thread pool, with each thread handling the strings of a distinct
+    /// range of hashes; only the (unique) strings ending up in the `Lexicon`
+    /// are then copied sequentially. Small slices (or a single thread) are
+    /// simply interned sequentially.
+    ///
+    /// # Panics
+    /// Panics if `strings` holds more unique strings than there are symbols.
+    pub fn par_from_slice<S: AsRef<str> + Sync>(strings: &[S]) -> (Lexicon, Vec<Symbol>) {
+        let mut lexicon = Lexicon::default();
+        let threads = rayon::current_num_threads();
+        if threads == 1 || strings.len() < SEQUENTIAL_LEN {
+            let syms = lexicon.intern_all(strings);
+            return (lexicon, syms);
+        }
+        let hasher = &lexicon.hasher;
+        let hashes = strings
+            .par_iter()
+            .map(|string| hasher.hash_one(string.as_ref()))
+            .collect::<Vec<_>>();
+
+        // partitions are told apart by bits which hash tables neither probe
+        // with (the lowest ones) nor tag their entries with (the highest
+        // ones), so that strings spread out evenly within each partition
+        let partitions = (threads * 4).next_power_of_two();
+        let partition_of = |hash: u64| (hash >> 32) as usize & (partitions - 1);
+
+        // indices of the strings of every partition, in ascending order,
+        // scattered in a single pass by chunks of `hashes`
+        let chunk_len = strings.len().div_ceil(threads);
+        let chunks = hashes
+            .par_chunks(chunk_len)
+            .enumerate()
+            .map(|(chunk, hashes)| {
+                let mut buckets = alloc::vec![Vec::new(); partitions];
+                for (idx, &hash) in hashes.iter().enumerate() {
+                    buckets[partition_of(hash)].push(chunk * chunk_len + idx);
+                }
+                buckets
+            })
+            .collect::<Vec<_>>();
+
+        // index of the first occurrence of every string
+        let first_of = strings
+            .iter()
+            .map(|_| AtomicUsize::new(0))
+            .collect::<Vec<_>>();
+        (0..partitions).into_par_iter().for_each(|partition| {
+            let indices = chunks.iter().flat_map(|buckets| &buckets[partition]);
+            let len = chunks.iter().map(|buckets| buckets[partition].len()).sum();
+            let mut table = HashTable::<usize>::with_capacity(len);
+            for &idx in indices {
+                let (hash, string) = (hashes[idx], strings[idx].as_ref());
+                let first = table.find(hash, |&first| strings[first].as_ref() == string);
+                let first = match first {
+                    Some(&first) => first,
+                    None => *table.insert_unique(hash, idx, |&i| hashes[i]).get(),
+                };
+                first_of[idx].store(first, Ordering::Relaxed);
+            }
+        });
+        let first_of = first_of
+            .into_iter()
+            .map(AtomicUsize::into_inner)
+            .collect::<Vec<_>>();
+
+        // only first occurrences are interned, in order, and every other
+        // occurrence is given their symbol
+        let mut syms = alloc::vec![Symbol::new(0); strings.len()];
+        for (idx, &first) in first_of.iter().enumerate() {
+            if first == idx {
+                let string = strings[idx].as_ref();
+                syms[idx] = match lexicon.intern_hashed(hashes[idx], string, |b, s| b.alloc(s)) {
+                    Ok(sym) => sym,
+                    Err((e, _)) => panic!("{} while interning `{}`", e, string),
+                };
+            }
+        }
+        let syms = first_of.par_iter().map(|&first| syms[first]).collect();
+        (lexicon, syms)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Instant;
+
+    fn strings(len: usize, unique: usize) -> Vec<String> {
+        (0..len)
+            .map(|i| format!("identifier_{}", i * 7919 % unique))
+            .collect()
+    }
+
+    fn par_from_slice_on(threads: usize, strings: &[String]) -> (Lexicon, Vec<Symbol>) {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        pool.install(|| Lexicon::par_from_slice(strings))
+    }
+
+    #[test]
+    fn test_par_from_slice() {
+        let strings = (0..10_000)
+            .map(|i| (i % 777).to_string())
+            .collect::<Vec<_>>();
+        let (lexicon, syms) = Lexicon::par_from_slice(&strings);
+        let sequential = strings.iter().collect::<Lexicon>();
+
+        assert_eq!(lexicon.len(), 777);
+        assert!(lexicon.diff(&sequential).is_empty());
+        for (string, sym) in strings.iter().zip(syms) {
+            assert_eq!(lexicon.lookup(sym), string);
+        }
+        lexicon.validate();
+    }
+
+    #[test]
+    fn test_par_from_slice_partitioned() {
+        let strings = strings(100_000, 30_011);
+        let sequential = strings.iter().collect::<Lexicon>();
+        for threads in [2, 7, 16] {
+            let (lexicon, syms) = par_from_slice_on(threads, &strings);
+            assert!(lexicon.diff(&sequential).is_empty());
+            for (string, sym) in strings.iter().zip(syms) {
+                assert_eq!(lexicon.lookup(sym), string);
+            }
+            lexicon.validate();
+        }
+    }
+
+    /// Compares against interning sequentially on as many threads as are
+    /// available. Run with `cargo test --release --features rayon -- --ignored`.
+    #[test]
+    #[ignore = "timing"]
+    fn bench_par_from_slice() {
+        let strings = strings(2_000_000, 500_009);
+        let start = Instant::now();
+        let mut sequential = Lexicon::default();
+        sequential.intern_all(&strings);
+        let sequential_time = start.elapsed();
+        let available = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let mut threads = 1;
+        while threads <= available {
+            let start = Instant::now();
+            let (lexicon, _) = par_from_slice_on(threads, &strings);
+            let time = start.elapsed();
+            assert_eq!(lexicon.len(), sequential.len());
+            // allowing for some noise
+            assert!(
+                time.as_secs_f64() <= sequential_time.as_secs_f64() * 1.25,
+                "{} threads took {:?}, against {:?} sequentially",
+                threads,
+                time,
+                sequential_time
+            );
+            threads *= 2;
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::ffi::OsStr;
+use std::path::Path;
+
+use crate::{ByteLexicon, LexiconError, OsSymbol};
+
+/// Interner for file paths (and other `OsStr`s), e.g., for build tools and
+/// language servers tracking the same paths over and over.
+///
+/// Paths are stored in their platform-specific encoding (as by
+/// `OsStr::as_encoded_bytes`), so paths which are not valid Unicode are
+/// interned and resolved losslessly on every platform. Paths are compared
+/// by that encoding only: `a/b` and `a//b` are distinct, so callers wanting
+/// them to be the same should normalize (or canonicalize) them first.
+#[derive(Clone, Debug)]
+pub struct PathLexicon {
+    inner: ByteLexicon<OsSymbol>,
+}
+
+impl PathLexicon {
+    pub fn new() -> Self {
+        Self {
+            inner: ByteLexicon::with_key(),
+        }
+    }
+
+    pub fn intern<P: AsRef<Path>>(&mut self, path: P) -> OsSymbol {
+        let path = path.as_ref();
+        match self.try_intern(path) {
+            Ok(id) => id,
+            Err(e) => panic!("{} while interning `{}`", e, path.display()),
+        }
+    }
+
+    /// Like `intern`, but returns an error rather than panicking if `path`
+    /// is new and cannot be interned (see `Lexicon::try_intern`).
+    pub fn try_intern<P: AsRef<Path>>(&mut self, path: P) -> Result<OsSymbol, LexiconError> {
+        self.inner
+            .try_intern(path.as_ref().as_os_str().as_encoded_bytes())
+    }
+
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> Option<OsSymbol> {
+        self.inner.get(path.as_ref().as_os_str().as_encoded_bytes())
+    }
+
+    pub fn lookup(&self, id: OsSymbol) -> &Path {
+        Path::new(self.lookup_os_str(id))
+    }
+
+    pub fn try_lookup(&self, id: OsSymbol) -> Option<&Path> {
+        let bytes = self.inner.try_lookup(id)?;
+        // only ever stores the encoded bytes of whole `OsStr`s
+        Some(Path::new(unsafe {
+            OsStr::from_encoded_bytes_unchecked(bytes)
+        }))
+    }
+
+    pub fn lookup_os_str(&self, id: OsSymbol) -> &OsStr {
+        match self.try_lookup(id) {
+            Some(path) => path.as_os_str(),
+            None => panic!("symbol `{}` was not interned", id.as_u32()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl Default for PathLexicon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::ops::Index<OsSymbol> for PathLexicon {
+    type Output = Path;
+
+    fn index(&self, index: OsSymbol) -> &Self::Output {
+        self.lookup(index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_paths() {
+        let mut lexicon = PathLexicon::new();
+        let main = lexicon.intern("src/main.rs");
+        assert_eq!(lexicon.intern(PathBuf::from("src").join("main.rs")), main);
+        assert_eq!(lexicon.intern(OsStr::new("src/main.rs")), main);
+        assert_eq!(&lexicon[main], Path::new("src/main.rs"));
+        assert_eq!(lexicon.get("src/lib.rs"), None);
+        assert_eq!(lexicon.len(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_unicode_paths() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let mut lexicon = PathLexicon::new();
+        let raw = OsStr::from_bytes(b"caf\xe9.txt");
+        let id = lexicon.intern(raw);
+        assert_ne!(lexicon.intern("caf\u{e9}.txt"), id);
+        assert_eq!(lexicon.lookup_os_str(id), raw);
+    }
+}
@@ -0,0 +1,221 @@
+use core::hash::BuildHasher;
+
+use crate::{Backend, Key, Lexicon};
+
+/// Pattern strings can be tested against, as by `Lexicon::find_matching`.
+///
+/// Implemented for `Glob`, as well as for `regex::Regex` with the `regex`
+/// feature enabled.
+pub trait Matcher {
+    fn matches(&self, string: &str) -> bool;
+}
+
+impl<M: Matcher + ?Sized> Matcher for &M {
+    fn matches(&self, string: &str) -> bool {
+        (**self).matches(string)
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Matcher for regex::Regex {
+    /// Whether the regex matches anywhere within `string`, as by
+    /// `Regex::is_match` (anchor it with `^...$` to match whole strings).
+    fn matches(&self, string: &str) -> bool {
+        self.is_match(string)
+    }
+}
+
+/// Shell-style glob, matching whole strings:
+///
+/// * `*` matches any (possibly empty) run of characters,
+/// * `?` matches any single character,
+/// * `[abc]` and `[a-z]` match any single character of the given set or
+///   range, and `[!a-z]` (or `[^a-z]`) any character outside of it,
+/// * `\` matches the character following it literally.
+///
+/// Any other character (including an unterminated `[`) matches itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Glob<'a>(&'a str);
+
+/// Single element of a `Glob`.
+enum Token<'a> {
+    Star,
+    Any,
+    Char(char),
+    /// The contents of a `[...]` class, and whether it is negated.
+    Class(&'a str, bool),
+}
+
+impl<'a> Glob<'a> {
+    pub fn new(pattern: &'a str) -> Self {
+        Self(pattern)
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+}
+
+/// Splits the first token off `pattern`.
+fn token(pattern: &str) -> Option<(Token<'_>, &str)> {
+    let mut chars = pattern.chars();
+    let token = match chars.next()? {
+        '*' => Token::Star,
+        '?' => Token::Any,
+        '\\' => match chars.next() {
+            Some(c) => Token::Char(c),
+            None => Token::Char('\\'),
+        },
+        '[' => {
+            let body = chars.as_str();
+            let (negated, skip) = match body.chars().next() {
+                Some('!' | '^') => (true, 1),
+                _ => (false, 0),
+            };
+            // a `]` right after the opening bracket is part of the class
+            let end = body[skip..]
+                .char_indices()
+                .skip(1)
+                .find(|&(_, c)| c == ']')
+                .map(|(idx, _)| skip + idx);
+            match end {
+                Some(end) => {
+                    return Some((Token::Class(&body[skip..end], negated), &body[end + 1..]));
+                }
+                None => Token::Char('['),
+            }
+        }
+        c => Token::Char(c),
+    };
+    Some((token, chars.as_str()))
+}
+
+impl Token<'_> {
+    fn matches(&self, c: char) -> bool {
+        match *self {
+            Token::Star | Token::Any => true,
+            Token::Char(expected) => c == expected,
+            Token::Class(body, negated) => {
+                let mut chars = body.chars();
+                let mut found = false;
+                while let Some(start) = chars.next() {
+                    let mut range = chars.clone();
+                    if let (Some('-'), Some(end)) = (range.next(), range.next()) {
+                        chars = range;
+                        found |= (start..=end).contains(&c);
+                    } else {
+                        found |= start == c;
+                    }
+                }
+                found != negated
+            }
+        }
+    }
+}
+
+impl Matcher for Glob<'_> {
+    fn matches(&self, string: &str) -> bool {
+        let (mut pattern, mut string) = (self.0, string);
+        // where to resume after the latest `*`, should the rest not match:
+        // the pattern following it, and the string past one more character
+        let mut resume = None;
+        loop {
+            match token(pattern) {
+                Some((Token::Star, rest)) => {
+                    resume = Some((rest, string));
+                    pattern = rest;
+                    continue;
+                }
+                Some((token, rest)) => {
+                    if let Some(c) = string.chars().next().filter(|&c| token.matches(c)) {
+                        pattern = rest;
+                        string = &string[c.len_utf8()..];
+                        continue;
+                    }
+                }
+                None if string.is_empty() => return true,
+                None => {}
+            }
+            // let the latest `*` match one more character, if any is left
+            let (after_star, skipped) = match resume {
+                Some(resume) => resume,
+                None => return false,
+            };
+            let c = match skipped.chars().next() {
+                Some(c) => c,
+                None => return false,
+            };
+            string = &skipped[c.len_utf8()..];
+            pattern = after_star;
+            resume = Some((after_star, string));
+        }
+    }
+}
+
+impl<K: Key, H: BuildHasher, B: Backend> Lexicon<K, H, B> {
+    /// Returns an iterator over every interned string matching `pattern`
+    /// (e.g., a `Glob` such as `*_impl`), along with its key, in the order
+    /// they were interned.
+    pub fn find_matching<'a, M: Matcher + 'a>(
+        &'a self,
+        pattern: M,
+    ) -> impl Iterator<Item = (K, &'a str)> + 'a {
+        self.iter()
+            .filter(move |(_, string)| pattern.matches(string))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_glob() {
+        let cases = [
+            ("*_impl", "fmt_impl", true),
+            ("*_impl", "fmt_impls", false),
+            ("a*b*c", "aXbYbZc", true),
+            ("a*b*c", "acb", false),
+            ("?x", "\u{e9}x", true),
+            ("[a-c]?", "bz", true),
+            ("[!a-c]*", "bz", false),
+            ("[]]", "]", true),
+            ("\\*", "*", true),
+            ("\\*", "a", false),
+            ("[ab", "[ab", true),
+            ("*", "", true),
+            ("", "a", false),
+        ];
+        for (pattern, string, expected) in cases {
+            assert_eq!(
+                Glob::new(pattern).matches(string),
+                expected,
+                "{} ~ {}",
+                pattern,
+                string
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_matching() {
+        let mut lexicon = Lexicon::default();
+        let ids = lexicon.intern_all(["fmt_impl", "impl", "debug_impl", "imply"]);
+        let matches = lexicon
+            .find_matching(Glob::new("*_impl"))
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+        assert_eq!(matches, [ids[0], ids[2]]);
+        assert_eq!(lexicon.find_matching(&Glob::new("impl?")).count(), 1);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_find_matching_regex() {
+        let mut lexicon = Lexicon::default();
+        lexicon.intern_all(["x1", "x22", "y3"]);
+        let regex = regex::Regex::new(r"^x\d+$").unwrap();
+        assert_eq!(lexicon.find_matching(&regex).count(), 2);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{Lexicon, Symbol};
+
+/// Set of strings whose `Symbol`s are known at compile time, as declared
+/// through the `symbols!` macro.
+pub trait Predefined {
+    /// The predefined strings, with string `i` having the symbol of index
+    /// `i` in a `Lexicon` created by `Lexicon::with_predefined`.
+    const STRINGS: &'static [&'static str];
+}
+
+/// Declares a set of strings along with constants holding their `Symbol`s,
+/// valid for a `Lexicon` created through `Lexicon::with_predefined` (akin to
+/// the predefined symbols of `rustc`):
+///
+/// ```
+/// use lexicon::{symbols, Lexicon};
+///
+/// symbols! {
+///     pub Kw {
+///         Fn: "fn",
+///         Let: "let",
+///     }
+/// }
+///
+/// let mut lexicon = Lexicon::with_predefined::<Kw>();
+/// assert_eq!(lexicon.intern("let"), Kw::Let);
+/// assert_eq!(lexicon.lookup(Kw::Fn), "fn");
+/// ```
+///
+/// This defines a unit struct (`Kw`) implementing `Predefined`, with an
+/// associated constant per string. Every string must be distinct.
+#[macro_export]
+macro_rules! symbols {
+    ($vis:vis $set:ident { $($name:ident: $string:expr),* $(,)? }) => {
+        $vis struct $set;
+
+        #[allow(dead_code, non_upper_case_globals)]
+        impl $set {
+            $crate::symbols!(@consts (0); $($name,)*);
+        }
+
+        impl $crate::Predefined for $set {
+            const STRINGS: &'static [&'static str] = &[$($string),*];
+        }
+    };
+    (@consts ($idx:expr); $name:ident, $($rest:ident,)*) => {
+        pub const $name: $crate::Symbol = $crate::Symbol::predefined($idx);
+        $crate::symbols!(@consts ($idx + 1); $($rest,)*);
+    };
+    (@consts ($idx:expr);) => {};
+}
+
+impl Symbol {
+    /// Returns the symbol with index `idx`, for use by `symbols!`.
+    #[doc(hidden)]
+    pub const fn predefined(idx: u32) -> Symbol {
+        match core::num::NonZeroU32::new(idx.wrapping_add(1)) {
+            Some(idx) => Symbol(idx),
+            None => panic!("too many predefined symbols"),
+        }
+    }
+}
+
+impl Lexicon {
+    /// Creates a new `Lexicon` holding the strings of `P` (declared through
+    /// `symbols!`) as its first strings, such that the constants generated
+    /// for them are valid for it.
+    ///
+    /// # Panics
+    /// Panics if any string of `P` is duplicated.
+    pub fn with_predefined<P: Predefined>() -> Self {
+        let mut lexicon = Lexicon::default();
+        for (idx, &string) in P::STRINGS.iter().enumerate() {
+            if lexicon.intern_static(string).as_usize() != idx {
+                panic!("predefined string `{}` is duplicated", string);
+            }
+        }
+        lexicon
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    symbols! {
+        Sym {
+            Main: "main",
+            SelfLower: "self",
+            Underscore: "_",
+        }
+    }
+
+    #[test]
+    fn test_predefined() {
+        let mut lexicon = Lexicon::with_predefined::<Sym>();
+        assert_eq!(Sym::Underscore.as_u32(), 2);
+        assert_eq!(lexicon.lookup(Sym::SelfLower), "self");
+        assert_eq!(lexicon.intern("main"), Sym::Main);
+        assert_eq!(lexicon.len(), Sym::STRINGS.len());
+    }
+
+    symbols! {
+        Dup { A: "a", B: "a" }
+    }
+
+    #[test]
+    #[should_panic(expected = "predefined string `a` is duplicated")]
+    fn test_duplicate_predefined() {
+        Lexicon::with_predefined::<Dup>();
+    }
+}
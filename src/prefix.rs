@@ -0,0 +1,154 @@
+use core::hash::BuildHasher;
+
+use crate::trie::Trie;
+use crate::{Backend, Key, Lexicon};
+
+impl<K: Key, H: BuildHasher, B: Backend> Lexicon<K, H, B> {
+    /// Starts maintaining a trie of every string of this `Lexicon`, which
+    /// `symbols_with_prefix` then answers queries from (e.g., for IDE-style
+    /// completion), visiting only the matching strings, in lexicographic
+    /// order. `symbols_with_prefix("")` thus traverses the whole `Lexicon`
+    /// in order.
+    ///
+    /// Interning a new string then also inserts it into the trie, in time
+    /// linear in its length. As the trie takes up a node per distinct
+    /// prefix, it is opt-in. Does nothing if the trie is already maintained.
+    pub fn index_prefixes(&mut self) {
+        if self.prefixes.is_some() {
+            return;
+        }
+        let mut trie = Trie::new();
+        for (idx, &span) in self.vec.iter().enumerate() {
+            if !self.pending.contains(&idx) {
+                let id = K::try_from_usize(idx).expect("interned string has no key");
+                trie.insert(self.backend.get(span), id);
+            }
+        }
+        self.prefixes = Some(trie);
+    }
+
+    /// Whether the trie of `index_prefixes` is maintained.
+    pub fn has_prefix_index(&self) -> bool {
+        self.prefixes.is_some()
+    }
+
+    /// Returns an iterator over every interned string starting with `prefix`,
+    /// along with its key.
+    ///
+    /// If the trie of `index_prefixes` is maintained, only the matching
+    /// strings are visited, in lexicographic order. Otherwise, every string
+    /// is scanned, and the matches are listed in the order they were
+    /// interned.
+    pub fn symbols_with_prefix<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = (K, &'a str)> + 'a {
+        let (indexed, scanned) = match &self.prefixes {
+            Some(trie) => (Some(trie.with_prefix(prefix)), 0..0),
+            None => (None, 0..self.vec.len()),
+        };
+        let indexed = indexed
+            .into_iter()
+            .flatten()
+            .map(move |id| (id, self.lookup(id)));
+        let scanned = scanned
+            .filter(move |idx| !self.pending.contains(idx))
+            .map(move |idx| {
+                let id = K::try_from_usize(idx).expect("interned string has no key");
+                (id, self.lookup(id))
+            })
+            .filter(move |(_, string)| string.starts_with(prefix));
+        indexed.chain(scanned)
+    }
+
+    /// Returns the key of the longest interned string which `input` starts
+    /// with, along with its length (e.g., for a maximal-munch lexer matching
+    /// operators), or `None` if no interned string is a prefix of `input`.
+    ///
+    /// Takes time linear in the length of the match if the trie of
+    /// `index_prefixes` is maintained, and scans every string otherwise.
+    pub fn longest_prefix_of(&self, input: &str) -> Option<(K, usize)> {
+        if let Some(trie) = &self.prefixes {
+            return trie.longest_prefix_of(input);
+        }
+        (0..self.vec.len())
+            .filter(|idx| !self.pending.contains(idx))
+            .map(|idx| (idx, self.backend.get(self.vec[idx])))
+            .filter(|(_, string)| input.starts_with(string))
+            .max_by_key(|(_, string)| string.len())
+            .map(|(idx, string)| {
+                let id = K::try_from_usize(idx).expect("interned string has no key");
+                (id, string.len())
+            })
+    }
+
+    /// Adds the newly interned (or defined) `id` to the trie, if it is
+    /// maintained.
+    pub(crate) fn index_prefix(&mut self, id: K) {
+        if let Some(trie) = &mut self.prefixes {
+            trie.insert(self.backend.get(self.vec[id.to_usize()]), id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_symbols_with_prefix() {
+        let mut lexicon = Lexicon::default();
+        lexicon.intern_all(["print", "parse", "push", "pop"]);
+        let scanned = lexicon
+            .symbols_with_prefix("p")
+            .map(|(_, s)| s)
+            .collect::<Vec<_>>();
+        assert_eq!(scanned, ["print", "parse", "push", "pop"]);
+
+        lexicon.index_prefixes();
+        lexicon.intern_all(["println", "panic", "String"]);
+        let reserved = lexicon.reserve_symbol();
+        lexicon.define(reserved, "pr");
+        let indexed = lexicon
+            .symbols_with_prefix("pr")
+            .map(|(_, s)| s)
+            .collect::<Vec<_>>();
+        assert_eq!(indexed, ["pr", "print", "println"]);
+        assert_eq!(lexicon.symbols_with_prefix("").count(), 8);
+        assert_eq!(lexicon.symbols_with_prefix("q").count(), 0);
+    }
+
+    #[test]
+    fn test_longest_prefix_of() {
+        let mut lexicon = Lexicon::default();
+        let ops = ["<", "<<", "<<=", "=", "=="].map(|s| lexicon.intern(s));
+        for indexed in [false, true] {
+            if indexed {
+                lexicon.index_prefixes();
+            }
+            assert_eq!(lexicon.longest_prefix_of("<<= 1"), Some((ops[2], 3)));
+            assert_eq!(lexicon.longest_prefix_of("<<x"), Some((ops[1], 2)));
+            assert_eq!(lexicon.longest_prefix_of("=>"), Some((ops[3], 1)));
+            assert_eq!(lexicon.longest_prefix_of("x"), None);
+        }
+    }
+
+    #[test]
+    fn test_prefix_index_truncation() {
+        let mut lexicon = Lexicon::default();
+        lexicon.index_prefixes();
+        lexicon.intern("kept");
+        lexicon.push_scope();
+        lexicon.intern("kernel");
+        lexicon.pop_scope();
+        let matches = lexicon
+            .symbols_with_prefix("ke")
+            .map(|(_, s)| s)
+            .collect::<Vec<_>>();
+        assert_eq!(matches, ["kept"]);
+        lexicon.clear();
+        assert!(lexicon.has_prefix_index());
+        assert_eq!(lexicon.symbols_with_prefix("").count(), 0);
+    }
+}
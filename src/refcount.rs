@@ -0,0 +1,236 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use hashbrown::{DefaultHashBuilder, HashTable};
+
+use crate::{Key, LexiconError, Resolver, Symbol, Symbolic};
+
+/// Reference-counted string interner, e.g., for long-running servers whose
+/// interned strings would otherwise grow without bound.
+///
+/// Every `Symbol` carries a count of the references held to it: interning a
+/// string (or calling `retain`) adds one, and `release` drops one.
+/// `collect` then frees every string no longer referenced, and recycles
+/// their symbols for strings interned afterwards.
+///
+/// Unlike a `Lexicon`, strings are stored in allocations of their own, so
+/// that each of them can be freed on its own.
+#[derive(Clone, Debug, Default)]
+pub struct RcLexicon {
+    map: HashTable<Symbol>,
+    hasher: DefaultHashBuilder,
+    slots: Vec<Slot>,
+    /// Symbols freed by `collect`, to be reused.
+    free: Vec<Symbol>,
+}
+
+#[derive(Clone, Debug)]
+struct Slot {
+    /// `None` once freed by `collect`.
+    string: Option<Box<str>>,
+    count: u32,
+}
+
+impl RcLexicon {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Interns `string`, adding a reference to its `Symbol`.
+    pub fn intern(&mut self, string: &str) -> Symbol {
+        match self.try_intern(string) {
+            Ok(id) => id,
+            Err(e) => panic!("{} while interning `{}`", e, string),
+        }
+    }
+
+    /// Like `intern`, but returns `LexiconError::KeysExhausted` rather than
+    /// panicking if `string` is new and no `Symbol` is left for it.
+    pub fn try_intern(&mut self, string: &str) -> Result<Symbol, LexiconError> {
+        let hash = self.hasher.hash_one(string);
+        if let Some(id) = self.find(hash, string) {
+            self.retain(id);
+            return Ok(id);
+        }
+
+        let slot = Slot {
+            string: Some(string.into()),
+            count: 1,
+        };
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.slots[id.as_usize()] = slot;
+                id
+            }
+            None => {
+                let id =
+                    Symbol::try_from_usize(self.slots.len()).ok_or(LexiconError::KeysExhausted)?;
+                self.slots.push(slot);
+                id
+            }
+        };
+        let (slots, hasher) = (&self.slots, &self.hasher);
+        self.map.insert_unique(hash, id, |id| {
+            hasher.hash_one(slots[id.as_usize()].string())
+        });
+        Ok(id)
+    }
+
+    pub fn get(&self, string: &str) -> Option<Symbol> {
+        self.find(self.hasher.hash_one(string), string)
+    }
+
+    fn find(&self, hash: u64, string: &str) -> Option<Symbol> {
+        let slots = &self.slots;
+        self.map
+            .find(hash, |id| slots[id.as_usize()].string() == string)
+            .copied()
+    }
+
+    /// Adds a reference to `id`.
+    ///
+    /// # Panics
+    /// Panics if `id` is not interned (or already freed).
+    pub fn retain(&mut self, id: Symbol) {
+        let slot = self.slot_mut(id);
+        slot.count = slot.count.checked_add(1).expect("reference count overflow");
+    }
+
+    /// Drops a reference to `id`, returning the number of references left.
+    /// Its string is only freed by the next `collect`, so `id` may still be
+    /// resolved (or retained again) until then.
+    ///
+    /// # Panics
+    /// Panics if `id` is not interned (or already freed), or if it holds no
+    /// reference to drop.
+    pub fn release(&mut self, id: Symbol) -> u32 {
+        let slot = self.slot_mut(id);
+        slot.count = slot
+            .count
+            .checked_sub(1)
+            .expect("symbol released more often than retained");
+        slot.count
+    }
+
+    /// Returns the number of references to `id`, or `0` if it is not
+    /// interned.
+    pub fn ref_count(&self, id: Symbol) -> u32 {
+        match self.slots.get(id.as_usize()) {
+            Some(slot) if slot.string.is_some() => slot.count,
+            _ => 0,
+        }
+    }
+
+    fn slot_mut(&mut self, id: Symbol) -> &mut Slot {
+        match self.slots.get_mut(id.as_usize()) {
+            Some(slot) if slot.string.is_some() => slot,
+            _ => panic!("symbol `{}` was not interned", id.as_u32()),
+        }
+    }
+
+    /// Frees the string of every `Symbol` without references, returning how
+    /// many were freed. Their symbols are invalidated, and will be handed
+    /// out again to strings interned later.
+    pub fn collect(&mut self) -> usize {
+        let freed = self.free.len();
+        let (map, hasher) = (&mut self.map, &self.hasher);
+        for (idx, slot) in self.slots.iter_mut().enumerate() {
+            if slot.count > 0 {
+                continue;
+            }
+            if let Some(string) = slot.string.take() {
+                let id = Symbol::new(idx as u32);
+                if let Ok(entry) = map.find_entry(hasher.hash_one(&*string), |&other| other == id) {
+                    entry.remove();
+                }
+                self.free.push(id);
+            }
+        }
+        self.free.len() - freed
+    }
+
+    pub fn lookup(&self, id: Symbol) -> &str {
+        match self.try_lookup(id) {
+            Some(string) => string,
+            None => panic!("symbol `{}` was not interned", id.as_u32()),
+        }
+    }
+
+    pub fn try_lookup(&self, id: Symbol) -> Option<&str> {
+        self.slots.get(id.as_usize())?.string.as_deref()
+    }
+
+    /// Returns the number of strings currently interned, i.e., not freed.
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Slot {
+    fn string(&self) -> &str {
+        self.string
+            .as_deref()
+            .expect("freed symbol is still mapped")
+    }
+}
+
+impl<S: Symbolic> core::ops::Index<S> for RcLexicon {
+    type Output = str;
+
+    fn index(&self, index: S) -> &Self::Output {
+        self.lookup(index.get_symbol())
+    }
+}
+
+impl Resolver for RcLexicon {
+    fn resolve(&self, id: Symbol) -> &str {
+        self.lookup(id)
+    }
+
+    fn try_resolve(&self, id: Symbol) -> Option<&str> {
+        self.try_lookup(id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_collect() {
+        let mut lexicon = RcLexicon::new();
+        let a = lexicon.intern("a");
+        let b = lexicon.intern("b");
+        assert_eq!(lexicon.intern("a"), a);
+        assert_eq!(lexicon.ref_count(a), 2);
+
+        assert_eq!(lexicon.release(a), 1);
+        assert_eq!(lexicon.release(b), 0);
+        assert_eq!(lexicon.collect(), 1);
+        assert_eq!(lexicon.try_lookup(b), None);
+        assert_eq!(lexicon.get("b"), None);
+        assert_eq!(&lexicon[a], "a");
+        assert_eq!(lexicon.len(), 1);
+
+        // the freed symbol is recycled
+        let c = lexicon.intern("c");
+        assert_eq!(c, b);
+        assert_eq!(lexicon.lookup(c), "c");
+        assert_eq!(lexicon.collect(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_release_freed() {
+        let mut lexicon = RcLexicon::new();
+        let a = lexicon.intern("a");
+        lexicon.release(a);
+        lexicon.collect();
+        lexicon.release(a);
+    }
+}
@@ -0,0 +1,221 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+use core::mem;
+
+use crate::trie::Trie;
+use crate::{Backend, Lexicon, Symbol, Symbolic};
+
+/// Mapping from the `Symbol`s of one symbol space to those of another, e.g.
+/// as returned by `Lexicon::merge`. Callers apply it to their own data
+/// structures to translate symbols between the two spaces.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SymbolRemap {
+    /// The new symbol of old symbol `i`, if it still has one.
+    map: Vec<Option<Symbol>>,
+}
+
+impl SymbolRemap {
+    pub(crate) fn with_capacity(cap: usize) -> Self {
+        Self {
+            map: Vec::with_capacity(cap),
+        }
+    }
+
+    pub(crate) fn push(&mut self, new: Option<Symbol>) {
+        self.map.push(new);
+    }
+
+    /// Creates a remapping sending every old symbol `i` to `map[i]`.
+    pub(crate) fn from_map(map: Vec<Option<Symbol>>) -> Self {
+        Self { map }
+    }
+
+    /// Returns the new `Symbol` of `old`, or `None` if `old` does not map to
+    /// any symbol.
+    pub fn get<S: Symbolic>(&self, old: S) -> Option<Symbol> {
+        self.map.get(old.get_symbol().as_usize()).copied().flatten()
+    }
+
+    /// Returns the number of old symbols covered by this remapping.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Whether every old symbol maps to itself, in which case callers need not
+    /// update their data at all.
+    pub fn is_identity(&self) -> bool {
+        self.map
+            .iter()
+            .enumerate()
+            .all(|(i, new)| new.map(|s| s.as_usize()) == Some(i))
+    }
+}
+
+impl<S: Symbolic> core::ops::Index<S> for SymbolRemap {
+    type Output = Symbol;
+
+    fn index(&self, old: S) -> &Self::Output {
+        let old = old.get_symbol();
+        match self.map.get(old.as_usize()) {
+            Some(Some(new)) => new,
+            _ => panic!("symbol `{}` has no remapping", old.as_u32()),
+        }
+    }
+}
+
+impl Lexicon {
+    /// Interns every string of `other` into this `Lexicon`, returning the
+    /// remapping from `other`'s symbols to their symbols in `self`. Strings
+    /// present in both keep their existing `Symbol` in `self`, while symbols
+    /// reserved in `other` (see `reserve_symbol`) are given newly reserved
+    /// symbols in `self`.
+    ///
+    /// This allows, e.g., per-thread lexicons to be combined once parallel
+    /// parsing is done.
+    pub fn merge(&mut self, other: &Lexicon) -> SymbolRemap {
+        let mut remap = SymbolRemap::with_capacity(other.symbol_count());
+        for string in other.slots() {
+            let new = match string {
+                Some(string) => self.intern(string),
+                None => self.reserve_symbol(),
+            };
+            remap.push(Some(new));
+        }
+        remap
+    }
+}
+
+impl<H: BuildHasher, B: Backend> Lexicon<Symbol, H, B> {
+    /// Drops every string for which `keep` returns `false`, compacting the
+    /// remaining ones (in order) into fresh storage, and returns the
+    /// remapping from the current symbols to the new ones, under which the
+    /// dropped symbols map to `None`. Callers apply it to their own data
+    /// structures, collecting garbage without rebuilding the `Lexicon`.
+    ///
+    /// Reserved symbols (see `reserve_symbol`) are kept as they are, without
+    /// consulting `keep`, while aliases are kept along with the symbol they
+    /// resolve to. Every checkpoint (and open scope) is invalidated.
+    pub fn retain(&mut self, mut keep: impl FnMut(Symbol, &str) -> bool) -> SymbolRemap {
+        let kept = self
+            .slots()
+            .enumerate()
+            .map(|(idx, string)| string.is_none_or(|string| keep(Symbol::new(idx as u32), string)))
+            .collect::<Vec<_>>();
+        let len = self
+            .slots()
+            .zip(&kept)
+            .filter(|&(_, &kept)| kept)
+            .map(|(string, _)| string.map_or(0, str::len))
+            .sum::<usize>();
+
+        let vec = mem::take(&mut self.vec);
+        let backend = mem::replace(&mut self.backend, B::with_capacity(len));
+        let aliases = mem::take(&mut self.aliases);
+        let pending = mem::take(&mut self.pending);
+        let keywords = mem::take(&mut self.keywords);
+        // the strings kept are not new, so observers are not told about them
+        let observers = mem::take(&mut self.observers);
+        let mut side_tables = mem::take(&mut self.side_tables);
+        let mut frequencies = mem::take(&mut self.frequencies);
+        self.map.clear();
+        self.alias_map.clear();
+        self.scopes.clear();
+        self.defined.clear();
+        self.stored = 0;
+        self.sorted.invalidate();
+        if let Some(trie) = &mut self.prefixes {
+            *trie = Trie::new();
+        }
+
+        let mut remap = SymbolRemap::with_capacity(vec.len());
+        for (idx, span) in vec.into_iter().enumerate() {
+            let new = match kept[idx] {
+                false => None,
+                true if pending.contains(&idx) => Some(self.reserve_symbol()),
+                true => Some(self.intern(backend.get(span))),
+            };
+            if new.is_some() && idx < keywords {
+                self.keywords += 1;
+            }
+            remap.push(new);
+        }
+        for (span, id) in aliases {
+            if let Some(new) = remap.get(id) {
+                self.alias(new, backend.get(span));
+            }
+        }
+        self.observers = observers;
+        side_tables.retain(&kept);
+        self.side_tables = side_tables;
+        frequencies.retain(&kept);
+        self.frequencies = frequencies;
+        remap
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_merge() {
+        let mut left = ["a", "b"].iter().collect::<Lexicon>();
+        let right = ["b", "c", "a"].iter().collect::<Lexicon>();
+
+        let remap = left.merge(&right);
+        assert_eq!(remap.len(), 3);
+        assert!(!remap.is_identity());
+        for (sym, string) in &right {
+            assert_eq!(left.lookup(remap[sym]), string);
+        }
+        assert_eq!(remap.get(Symbol::new(3)), None);
+        left.validate();
+    }
+
+    #[test]
+    fn test_merge_reserved() {
+        let mut lexicon = Lexicon::default();
+        let a = lexicon.intern("a");
+        let reserved = [lexicon.reserve_symbol(), lexicon.reserve_symbol()];
+
+        let mut other = ["b"].iter().collect::<Lexicon>();
+        let remap = other.merge(&lexicon);
+        assert_eq!(other.lookup(remap[a]), "a");
+        assert_eq!(other.get(""), None);
+        assert_ne!(remap[reserved[0]], remap[reserved[1]]);
+        assert!(reserved.iter().all(|&id| other.is_reserved(remap[id])));
+        other.validate();
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut lexicon = Lexicon::with_keywords(&["fn", "let"]);
+        let ids = lexicon.intern_all(["tmp0", "x", "tmp1", "y"]);
+        let reserved = lexicon.reserve_symbol();
+        lexicon.alias(ids[1], "ex");
+        lexicon.alias(ids[0], "t");
+
+        let remap = lexicon.retain(|_, s| !s.starts_with("tmp") && s != "let");
+        assert_eq!(remap.get(ids[0]), None);
+        assert_eq!(lexicon.lookup(remap[ids[1]]), "x");
+        assert_eq!(remap[ids[3]], Symbol::new(2));
+        assert!(lexicon.is_reserved(remap[reserved]));
+        assert_eq!(lexicon.get("ex"), Some(remap[ids[1]]));
+        assert_eq!(lexicon.get("t"), None);
+        assert_eq!(lexicon.keyword_limit(), 1);
+        assert_eq!(lexicon.len(), 3);
+        assert_eq!(lexicon.symbol_count(), 4);
+        lexicon.validate();
+    }
+
+    #[test]
+    fn test_merge_identity() {
+        let mut lexicon = ["x", "y"].iter().collect::<Lexicon>();
+        let copy = ["x", "y"].iter().collect::<Lexicon>();
+        assert!(lexicon.merge(&copy).is_identity());
+    }
+}
@@ -0,0 +1,125 @@
+use core::hash::BuildHasher;
+
+use crate::{Backend, Key, Lexicon, LexiconError};
+
+impl<K: Key, H: BuildHasher, B: Backend> Lexicon<K, H, B> {
+    /// Hands out a new key whose string is not known yet, to be given later
+    /// through `define` (e.g., for generated names only spelled out at the
+    /// end of lowering). Until then, the key cannot be looked up, and is
+    /// skipped when iterating over the `Lexicon`, while encodings of it
+    /// (e.g., `write_to`) restore it as reserved.
+    ///
+    /// # Panics
+    /// Panics if every key of type `K` has already been handed out.
+    pub fn reserve_symbol(&mut self) -> K {
+        let id = match K::try_from_usize(self.vec.len()) {
+            Some(id) => id,
+            None => panic!("{} while reserving a symbol", LexiconError::KeysExhausted),
+        };
+        let span = self.backend.alloc("");
+        self.vec.push(span);
+        self.side_tables.push_default();
+        self.frequencies.push(0);
+        self.pending.insert(id.to_usize());
+        id
+    }
+
+    /// Whether `id` was handed out by `reserve_symbol`, but not yet defined.
+    pub fn is_reserved(&self, id: K) -> bool {
+        self.pending.contains(&id.to_usize())
+    }
+
+    /// Defines `string` as the string of the reserved key `id`. Truncating
+    /// this `Lexicon` back to a checkpoint taken before `id` was defined
+    /// makes `id` reserved again.
+    ///
+    /// # Panics
+    /// Panics if `id` is not reserved (or already defined), or if `string`
+    /// is already interned. See `try_define` for a non-panicking alternative.
+    pub fn define(&mut self, id: K, string: &str) {
+        if let Err(e) = self.try_define(id, string) {
+            panic!("{} while defining `{}`", e, string)
+        }
+    }
+
+    /// Like `define`, but returns an error (leaving this `Lexicon` untouched)
+    /// rather than panicking if `id` is not reserved
+    /// (`LexiconError::NotReserved`), if `string` is already interned
+    /// (`LexiconError::AlreadyInterned`), or if `string` is too long
+    /// (`LexiconError::StringTooLong`) or exceeds the memory limit
+    /// (`LexiconError::MemoryLimitExceeded`).
+    pub fn try_define(&mut self, id: K, string: &str) -> Result<(), LexiconError> {
+        let idx = id.to_usize();
+        if !self.pending.contains(&idx) {
+            return Err(LexiconError::NotReserved(idx));
+        }
+        let hash = self.hasher.hash_one(string);
+        if let Some(other) = self.find(hash, string) {
+            return Err(LexiconError::AlreadyInterned(other.to_usize()));
+        }
+        if string.len() > B::MAX_LEN {
+            return Err(LexiconError::StringTooLong);
+        }
+        self.charge(string.len())?;
+
+        self.vec[idx] = self.backend.alloc(string);
+        self.pending.remove(&idx);
+        self.defined.insert(idx);
+        self.sorted.invalidate();
+        let (vec, backend, hasher) = (&self.vec, &self.backend, &self.hasher);
+        self.map.insert_unique(hash, id, |id| {
+            hasher.hash_one(backend.get(vec[id.to_usize()]))
+        });
+        self.index_prefix(id);
+        self.frequencies.hit(idx);
+        self.notify(id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Symbol;
+
+    #[test]
+    fn test_reserve_and_define() {
+        let mut lexicon = Lexicon::default();
+        let anon = lexicon.reserve_symbol();
+        let named = lexicon.intern("named");
+        assert!(lexicon.is_reserved(anon));
+        assert_eq!(lexicon.try_lookup(anon), None);
+        assert_eq!(lexicon.get(""), None);
+
+        lexicon.define(anon, "__anon0");
+        assert!(!lexicon.is_reserved(anon));
+        assert_eq!(lexicon.lookup(anon), "__anon0");
+        assert_eq!(lexicon.get("__anon0"), Some(anon));
+        assert_eq!(named, Symbol::new(1));
+        lexicon.validate();
+    }
+
+    #[test]
+    fn test_define_errors() {
+        let mut lexicon = Lexicon::default();
+        let taken = lexicon.intern("taken");
+        let id = lexicon.reserve_symbol();
+        assert_eq!(
+            lexicon.try_define(id, "taken"),
+            Err(LexiconError::AlreadyInterned(0))
+        );
+        assert_eq!(
+            lexicon.try_define(taken, "other"),
+            Err(LexiconError::NotReserved(0))
+        );
+
+        let checkpoint = lexicon.checkpoint();
+        let dropped = lexicon.reserve_symbol();
+        lexicon.define(id, "late");
+        lexicon.truncate_to(checkpoint);
+        assert!(!lexicon.is_reserved(dropped));
+        assert!(lexicon.is_reserved(id));
+        assert_eq!(lexicon.get("late"), None);
+        lexicon.validate();
+    }
+}
@@ -0,0 +1,76 @@
+use alloc::vec::Vec;
+
+use crate::{Key, Symbol};
+
+/// Read-only access to the strings behind the keys of an interner.
+///
+/// Implemented by every interner able to resolve keys through a shared
+/// reference (`Lexicon`, `FrozenLexicon`, `MappedLexicon`, ...), and object
+/// safe, so that code merely displaying symbols (e.g., pretty-printers) may
+/// take a `&dyn Resolver` rather than a concrete interner type.
+pub trait Resolver<K: Key = Symbol> {
+    /// Returns the string corresponding to `id`.
+    ///
+    /// # Panics
+    /// Panics if `id` cannot be resolved, e.g., if it was not interned.
+    fn resolve(&self, id: K) -> &str;
+
+    /// Returns the string corresponding to `id`, or `None` if it cannot be
+    /// resolved.
+    fn try_resolve(&self, id: K) -> Option<&str>;
+
+    /// Returns the strings corresponding to `ids`, in order.
+    ///
+    /// # Panics
+    /// Panics if any of `ids` cannot be resolved.
+    fn resolve_many(&self, ids: &[K]) -> Vec<&str> {
+        ids.iter().map(|&id| self.resolve(id)).collect()
+    }
+
+    /// Lazily resolves every key of `ids`, e.g., to write the strings of a
+    /// long sequence of symbols without collecting them first.
+    ///
+    /// # Panics
+    /// The returned iterator panics upon reaching a key that cannot be
+    /// resolved.
+    fn resolve_iter<'a, I>(&'a self, ids: I) -> impl Iterator<Item = &'a str>
+    where
+        I: IntoIterator<Item = K>,
+        I::IntoIter: 'a,
+        Self: Sized,
+    {
+        ids.into_iter().map(move |id| self.resolve(id))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Lexicon;
+    use alloc::string::String;
+
+    fn join(resolver: &dyn Resolver, syms: &[Symbol]) -> String {
+        let strings = syms.iter().map(|&sym| resolver.resolve(sym));
+        strings.collect::<Vec<_>>().join(" ")
+    }
+
+    #[test]
+    fn test_dyn_resolver() {
+        let mut lexicon = Lexicon::default();
+        let syms = ["let", "x", "=", "1"].map(|s| lexicon.intern(s));
+        assert_eq!(join(&lexicon, &syms), "let x = 1");
+        assert_eq!(lexicon.try_resolve(Symbol::new(4)), None);
+
+        let frozen = lexicon.freeze();
+        assert_eq!(join(&frozen, &syms), "let x = 1");
+    }
+
+    #[test]
+    fn test_resolve_many() {
+        let mut lexicon = Lexicon::default();
+        let syms = ["a", "b", "a"].map(|s| lexicon.intern(s));
+        assert_eq!(lexicon.resolve_many(&syms), ["a", "b", "a"]);
+        let resolved = lexicon.resolve_iter(syms.iter().rev().copied());
+        assert_eq!(resolved.collect::<String>(), "aba");
+    }
+}
@@ -0,0 +1,169 @@
+use core::ops::Index;
+
+use rkyv::bytecheck::CheckBytes;
+use rkyv::munge::munge;
+use rkyv::option::ArchivedOption;
+use rkyv::rancor::{Fallible, Source};
+use rkyv::ser::{Allocator, Writer};
+use rkyv::string::{ArchivedString, StringResolver};
+use rkyv::vec::{ArchivedVec, VecResolver};
+use rkyv::{Archive, Deserialize, Place, Portable, Serialize};
+
+use crate::{ArchivedSymbol, Lexicon, Symbol, Symbolic};
+
+impl Symbolic for ArchivedSymbol {
+    fn get_symbol(&self) -> Symbol {
+        Symbol(self.0.to_native())
+    }
+}
+
+/// Archived form of a `Lexicon`: its strings, laid out in insertion order so
+/// that every `Symbol` may be resolved straight out of the archive (e.g., out
+/// of a memory-mapped file) without deserializing anything. Symbols reserved
+/// but not defined yet (see `Lexicon::reserve_symbol`) have no string.
+#[derive(CheckBytes, Portable)]
+#[bytecheck(crate = rkyv::bytecheck)]
+#[repr(transparent)]
+pub struct ArchivedLexicon {
+    strings: ArchivedVec<ArchivedOption<ArchivedString>>,
+}
+
+impl ArchivedLexicon {
+    pub fn lookup(&self, id: Symbol) -> &str {
+        match self.try_lookup(id) {
+            Some(string) => string,
+            None => panic!("symbol `{}` was not interned", id.as_u32()),
+        }
+    }
+
+    /// Returns the string corresponding to `id`, or `None` if `id` was not
+    /// interned in the archived `Lexicon` (or only reserved in it).
+    pub fn try_lookup(&self, id: Symbol) -> Option<&str> {
+        let string = self.strings.get(id.as_usize())?.as_ref()?;
+        Some(string.as_str())
+    }
+
+    /// Whether `id` was reserved but not defined in the archived `Lexicon`.
+    pub fn is_reserved(&self, id: Symbol) -> bool {
+        self.strings
+            .get(id.as_usize())
+            .is_some_and(|string| string.is_none())
+    }
+
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+impl<S: Symbolic> Index<S> for ArchivedLexicon {
+    type Output = str;
+
+    fn index(&self, index: S) -> &Self::Output {
+        self.lookup(index.get_symbol())
+    }
+}
+
+impl Archive for Lexicon {
+    type Archived = ArchivedLexicon;
+    type Resolver = VecResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedLexicon { strings } = out);
+        ArchivedVec::resolve_from_len(self.symbol_count(), resolver, strings);
+    }
+}
+
+impl<S> Serialize<S> for Lexicon
+where
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedVec::<ArchivedOption<ArchivedString>>::serialize_from_iter::<Option<Str>, _, _>(
+            self.slots().map(|string| string.map(Str)),
+            serializer,
+        )
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Lexicon, D> for ArchivedLexicon {
+    fn deserialize(&self, _: &mut D) -> Result<Lexicon, D::Error> {
+        let mut lexicon = Lexicon::default();
+        for string in self.strings.iter() {
+            match string.as_ref() {
+                Some(string) => lexicon.intern(string.as_str()),
+                None => lexicon.reserve_symbol(),
+            };
+        }
+        Ok(lexicon)
+    }
+}
+
+/// Borrowed string archived as an `ArchivedString`, so that the strings of a
+/// `Lexicon` can be serialized without first being collected into `String`s.
+struct Str<'a>(&'a str);
+
+impl Archive for Str<'_> {
+    type Archived = ArchivedString;
+    type Resolver = StringResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedString::resolve_from_str(self.0, resolver, out);
+    }
+}
+
+impl<S> Serialize<S> for Str<'_>
+where
+    S: Fallible + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_from_str(self.0, serializer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rkyv::rancor::Error;
+
+    #[test]
+    fn test_archived_lookup() {
+        let mut lexicon = Lexicon::default();
+        let long = "a string too long to be stored inline within the archive";
+        let syms = ["x", long, ""].map(|s| lexicon.intern(s));
+
+        let bytes = rkyv::to_bytes::<Error>(&lexicon).unwrap();
+        let archived = rkyv::access::<ArchivedLexicon, Error>(&bytes).unwrap();
+        let sym_bytes = rkyv::to_bytes::<Error>(&syms).unwrap();
+        let archived_syms = rkyv::access::<[ArchivedSymbol; 3], Error>(&sym_bytes).unwrap();
+
+        assert_eq!(archived.len(), 3);
+        assert_eq!(&archived[archived_syms[0]], "x");
+        assert_eq!(&archived[archived_syms[1]], long);
+        assert_eq!(archived.lookup(syms[2]), "");
+
+        let restored = rkyv::deserialize::<Lexicon, Error>(archived).unwrap();
+        assert_eq!(restored.lookup(syms[1]), long);
+    }
+
+    #[test]
+    fn test_archived_reserved() {
+        let mut lexicon = Lexicon::default();
+        let reserved = [lexicon.reserve_symbol(), lexicon.reserve_symbol()];
+        let sym = lexicon.intern("x");
+
+        let bytes = rkyv::to_bytes::<Error>(&lexicon).unwrap();
+        let archived = rkyv::access::<ArchivedLexicon, Error>(&bytes).unwrap();
+        assert_eq!(archived.try_lookup(reserved[0]), None);
+        assert!(archived.is_reserved(reserved[1]) && !archived.is_reserved(sym));
+
+        let restored = rkyv::deserialize::<Lexicon, Error>(archived).unwrap();
+        assert!(reserved.iter().all(|&id| restored.is_reserved(id)));
+        assert_eq!(restored.lookup(sym), "x");
+    }
+}
@@ -0,0 +1,85 @@
+use alloc::boxed::Box;
+
+use crate::{LexiconError, PathSymbol, Symbol, ValueInterner};
+
+/// Interner for sequences of `Symbol`s, e.g., the segments of fully
+/// qualified names such as `std::vec::Vec`, each of which becomes a single
+/// `PathSymbol` that compares in constant time.
+///
+/// Not to be confused with `PathLexicon`, which interns file paths.
+#[derive(Clone, Debug, Default)]
+pub struct PathInterner {
+    paths: ValueInterner<Box<[Symbol]>, PathSymbol>,
+}
+
+impl PathInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&mut self, segments: &[Symbol]) -> PathSymbol {
+        match self.try_intern(segments) {
+            Ok(id) => id,
+            Err(e) => panic!("{} while interning a path", e),
+        }
+    }
+
+    /// Like `intern`, but returns `LexiconError::KeysExhausted` rather than
+    /// panicking if `segments` is new and no key is left for it.
+    pub fn try_intern(&mut self, segments: &[Symbol]) -> Result<PathSymbol, LexiconError> {
+        // only copies `segments` if they are new
+        match self.paths.get(segments) {
+            Some(id) => Ok(id),
+            None => self.paths.try_intern(segments.into()),
+        }
+    }
+
+    pub fn get(&self, segments: &[Symbol]) -> Option<PathSymbol> {
+        self.paths.get(segments)
+    }
+
+    /// Returns the segments of the path `id`.
+    pub fn lookup(&self, id: PathSymbol) -> &[Symbol] {
+        self.paths.lookup(id)
+    }
+
+    pub fn try_lookup(&self, id: PathSymbol) -> Option<&[Symbol]> {
+        self.paths.try_lookup(id).map(|segments| &segments[..])
+    }
+
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+}
+
+impl core::ops::Index<PathSymbol> for PathInterner {
+    type Output = [Symbol];
+
+    fn index(&self, index: PathSymbol) -> &Self::Output {
+        self.lookup(index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Lexicon;
+
+    #[test]
+    fn test_paths() {
+        let mut lexicon = Lexicon::default();
+        let mut paths = PathInterner::new();
+        let segments = ["std", "vec", "Vec"].map(|s| lexicon.intern(s));
+        let vec = paths.intern(&segments);
+        let module = paths.intern(&segments[..2]);
+        assert_ne!(vec, module);
+        assert_eq!(paths.intern(&segments), vec);
+        assert_eq!(paths.get(&segments[..2]), Some(module));
+        assert_eq!(lexicon.join(&paths[vec], "::"), "std::vec::Vec");
+        assert_eq!(paths.len(), 2);
+    }
+}
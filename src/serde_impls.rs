@@ -0,0 +1,197 @@
+use core::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+use crate::{Lexicon, Symbol};
+
+/// Symbols are serialized as their `u32` index, and are therefore only
+/// meaningful alongside the (serialized) `Lexicon` they were interned in.
+impl Serialize for Symbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.as_u32())
+    }
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let idx = u32::deserialize(deserializer)?;
+        Symbol::try_new(idx).ok_or_else(|| {
+            de::Error::invalid_value(de::Unexpected::Unsigned(idx.into()), &"a symbol index")
+        })
+    }
+}
+
+/// A `Lexicon` is serialized as the sequence of its strings in insertion
+/// order, so that deserializing it re-interns every string under the same
+/// `Symbol` it had before. Each string is serialized as an `Option<&str>`,
+/// with `None` standing for a symbol reserved but not defined yet (see
+/// `Lexicon::reserve_symbol`), which is reserved again when deserializing.
+impl Serialize for Lexicon {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.symbol_count()))?;
+        for string in self.slots() {
+            seq.serialize_element(&string)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Lexicon {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(LexiconVisitor)
+    }
+}
+
+struct LexiconVisitor;
+
+impl<'de> Visitor<'de> for LexiconVisitor {
+    type Value = Lexicon;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a sequence of unique strings")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut lexicon = Lexicon::default();
+        while let Some(sym) = seq.next_element_seed(SlotSeed(&mut lexicon))? {
+            // a duplicate would be mapped to the symbol of its first
+            // occurrence, shifting every symbol that follows it
+            if sym.as_usize() + 1 != lexicon.symbol_count() {
+                return Err(de::Error::custom(format_args!(
+                    "duplicate string `{}` in lexicon",
+                    lexicon.lookup(sym)
+                )));
+            }
+        }
+        Ok(lexicon)
+    }
+}
+
+/// Deserializes an element of a serialized `Lexicon`: either a string to be
+/// interned, or nothing, for a symbol to be reserved.
+struct SlotSeed<'a>(&'a mut Lexicon);
+
+impl<'de> de::DeserializeSeed<'de> for SlotSeed<'_> {
+    type Value = Symbol;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_option(self)
+    }
+}
+
+impl<'de> Visitor<'de> for SlotSeed<'_> {
+    type Value = Symbol;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an optional string")
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(self.0.reserve_symbol())
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        de::DeserializeSeed::deserialize(SymbolSeed(self.0), deserializer)
+    }
+}
+
+/// Serializes a `Symbol` as the string it resolves to within a given
+/// `Lexicon`, rather than as its (otherwise meaningless) raw value.
+#[derive(Clone, Copy, Debug)]
+pub struct SymbolSerializer<'a> {
+    pub lexicon: &'a Lexicon,
+    pub symbol: Symbol,
+}
+
+impl Serialize for SymbolSerializer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.lexicon.lookup(self.symbol))
+    }
+}
+
+/// Deserializes a string by interning it into the given `Lexicon`, producing
+/// its `Symbol`. The string is interned directly, without first being
+/// allocated as an owned `String`.
+#[derive(Debug)]
+pub struct SymbolSeed<'a>(pub &'a mut Lexicon);
+
+impl<'de> de::DeserializeSeed<'de> for SymbolSeed<'_> {
+    type Value = Symbol;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de> Visitor<'de> for SymbolSeed<'_> {
+    type Value = Symbol;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_str<E: de::Error>(self, string: &str) -> Result<Self::Value, E> {
+        Ok(self.0.intern(string))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lexicon_round_trip() {
+        let mut lexicon = Lexicon::default();
+        let syms = ["fn", "main", "x"].map(|s| lexicon.intern(s));
+
+        let json = serde_json::to_string(&(&lexicon, syms)).unwrap();
+        assert_eq!(json, r#"[["fn","main","x"],[0,1,2]]"#);
+
+        let (restored, restored_syms): (Lexicon, [Symbol; 3]) =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(syms, restored_syms);
+        for sym in syms {
+            assert_eq!(lexicon.lookup(sym), restored.lookup(sym));
+        }
+    }
+
+    #[test]
+    fn test_symbol_as_string() {
+        use serde::de::DeserializeSeed;
+
+        let mut lexicon = Lexicon::default();
+        let symbol = lexicon.intern("hello");
+        let json = serde_json::to_string(&SymbolSerializer {
+            lexicon: &lexicon,
+            symbol,
+        })
+        .unwrap();
+        assert_eq!(json, r#""hello""#);
+
+        let mut other = Lexicon::default();
+        other.intern("first");
+        let mut de = serde_json::Deserializer::from_str(&json);
+        let sym = SymbolSeed(&mut other).deserialize(&mut de).unwrap();
+        assert_eq!(other.lookup(sym), "hello");
+    }
+
+    #[test]
+    fn test_reserved_round_trip() {
+        let mut lexicon = Lexicon::default();
+        let a = lexicon.intern("a");
+        let reserved = [lexicon.reserve_symbol(), lexicon.reserve_symbol()];
+        let json = serde_json::to_string(&lexicon).unwrap();
+        assert_eq!(json, r#"["a",null,null]"#);
+
+        let restored = serde_json::from_str::<Lexicon>(&json).unwrap();
+        assert_eq!(restored.lookup(a), "a");
+        assert!(reserved.iter().all(|&id| restored.is_reserved(id)));
+        restored.validate();
+    }
+
+    #[test]
+    fn test_duplicate_rejected() {
+        assert!(serde_json::from_str::<Lexicon>(r#"["a","b","a"]"#).is_err());
+    }
+}
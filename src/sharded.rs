@@ -0,0 +1,217 @@
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::RwLock;
+
+use crate::concurrent::{default_shards, Shard};
+use crate::slots::Slots;
+use crate::{Lexicon, Resolver, Symbol, SymbolRemap};
+
+/// String interner split into fully independent shards, for heavily parallel
+/// workloads where even a single shared counter becomes a point of
+/// contention.
+///
+/// Unlike `ConcurrentLexicon`, each shard assigns identifiers from its own
+/// counter, with the shard a string was routed to being encoded in the low
+/// bits of its `Symbol`. Interning into different shards therefore never
+/// touches shared state, at the cost of `Symbol`s no longer being dense (nor
+/// valid for any other interner).
+pub struct ShardedLexicon {
+    shards: Box<[ShardSlots]>,
+    bits: u32,
+    hasher: RandomState,
+}
+
+struct ShardSlots {
+    shard: RwLock<Shard>,
+    slots: Slots,
+    next: AtomicU32,
+}
+
+impl ShardedLexicon {
+    /// Upper bound on the number of shards, leaving each shard with at least
+    /// `2^24` identifiers.
+    pub const MAX_SHARDS: usize = 256;
+
+    /// Creates a new `ShardedLexicon` with a number of shards scaled to the
+    /// available parallelism of the current machine.
+    pub fn new() -> Self {
+        Self::with_shards(default_shards())
+    }
+
+    /// Creates a new `ShardedLexicon` with (at least) `shards` shards. The
+    /// number of shards is always rounded up to a power of two, and may not
+    /// exceed `ShardedLexicon::MAX_SHARDS`.
+    pub fn with_shards(shards: usize) -> Self {
+        let shards = shards.clamp(1, Self::MAX_SHARDS).next_power_of_two();
+        Self {
+            shards: (0..shards)
+                .map(|_| ShardSlots {
+                    shard: Shard::new(),
+                    slots: Slots::new(),
+                    next: AtomicU32::new(0),
+                })
+                .collect(),
+            bits: shards.trailing_zeros(),
+            hasher: RandomState::new(),
+        }
+    }
+
+    pub fn intern(&self, string: &str) -> Symbol {
+        let idx = self.hasher.hash_one(string) as usize & (self.shards.len() - 1);
+        let ShardSlots { shard, slots, next } = &self.shards[idx];
+        if let Some(&id) = Shard::read(shard, string).map.get(string) {
+            return id;
+        }
+
+        let mut shard = Shard::write(shard, string);
+        if let Some(&id) = shard.map.get(string) {
+            return id;
+        }
+
+        let local = next.load(Ordering::Relaxed);
+        if local >= u32::MAX >> self.bits {
+            panic!("shard {} of `ShardedLexicon` is out of symbols", idx)
+        }
+        // the shard's arena is only dropped along with `self`, and `slots`
+        // never hands out references outliving `&self`
+        let string = unsafe { shard.arena.alloc_stable(string) };
+        slots.publish(local as usize, string);
+        next.store(local + 1, Ordering::Relaxed);
+
+        let id = Symbol::new(local << self.bits | idx as u32);
+        shard.map.insert(string, id);
+        id
+    }
+
+    pub fn lookup(&self, id: Symbol) -> &str {
+        match self.try_lookup(id) {
+            Some(string) => string,
+            None => panic!("symbol `{}` was not interned", id.as_u32()),
+        }
+    }
+
+    /// Returns the string corresponding to `id`, or `None` if `id` was not
+    /// (or not yet) interned in this `ShardedLexicon`.
+    pub fn try_lookup(&self, id: Symbol) -> Option<&str> {
+        let (shard, local) = self.split(id);
+        self.shards.get(shard).and_then(|s| s.slots.get(local))
+    }
+
+    /// Returns the index of the shard the given `Symbol` was interned in.
+    pub fn shard_of(&self, id: Symbol) -> usize {
+        self.split(id).0
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns the number of strings interned so far across all shards. Since
+    /// other threads may be interning concurrently, this should only be
+    /// treated as a snapshot.
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|s| s.next.load(Ordering::Relaxed) as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Converts this into a regular `Lexicon` holding every string in
+    /// lexicographic order, along with the remapping from the symbols handed
+    /// out by this interner (which aren't dense) to those of the `Lexicon`.
+    /// The result does not depend on how threads interleaved while
+    /// interning, keeping build outputs deterministic.
+    pub fn into_lexicon(self) -> (Lexicon, SymbolRemap) {
+        let shards = self
+            .shards
+            .into_vec()
+            .into_iter()
+            .map(|s| s.shard.into_inner().unwrap_or_else(|e| e.into_inner()))
+            .collect::<Vec<_>>();
+        // the strings are owned by the shards' arenas, kept alive until done
+        let mut entries = shards
+            .iter()
+            .flat_map(|shard| shard.map.iter().map(|(&s, &id)| (s, id)))
+            .collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|&(string, _)| string);
+
+        let len = entries.iter().map(|(_, id)| id.as_usize() + 1).max();
+        let mut map = vec![None; len.unwrap_or(0)];
+        let mut lexicon = Lexicon::with_capacity(Lexicon::BASE_CAPACITY);
+        for (string, id) in entries {
+            map[id.as_usize()] = Some(lexicon.intern(string));
+        }
+        (lexicon, SymbolRemap::from_map(map))
+    }
+
+    fn split(&self, id: Symbol) -> (usize, usize) {
+        let raw = id.as_u32();
+        let mask = (1 << self.bits) - 1;
+        ((raw & mask) as usize, (raw >> self.bits) as usize)
+    }
+}
+
+impl Default for ShardedLexicon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Resolver for ShardedLexicon {
+    fn resolve(&self, id: Symbol) -> &str {
+        self.lookup(id)
+    }
+
+    fn try_resolve(&self, id: Symbol) -> Option<&str> {
+        self.try_lookup(id)
+    }
+}
+
+impl std::fmt::Debug for ShardedLexicon {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShardedLexicon")
+            .field("shards", &self.shards.len())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_sharded_intern() {
+        let lexicon = Arc::new(ShardedLexicon::with_shards(8));
+        let handles = (0..4)
+            .map(|t| {
+                let lexicon = Arc::clone(&lexicon);
+                thread::spawn(move || {
+                    (0..500)
+                        .map(|i| (i, lexicon.intern(&format!("{}", i * (t % 2)))))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+        for (t, handle) in handles.into_iter().enumerate() {
+            for (i, sym) in handle.join().unwrap() {
+                assert_eq!(lexicon.lookup(sym), format!("{}", i * (t % 2)));
+                assert!(lexicon.shard_of(sym) < lexicon.shard_count());
+            }
+        }
+        assert_eq!(lexicon.len(), 500);
+
+        let ten = lexicon.intern("10");
+        let (flat, remap) = Arc::try_unwrap(lexicon).unwrap().into_lexicon();
+        assert_eq!(flat.len(), 500);
+        assert_eq!(flat.lookup(Symbol::new(2)), "10");
+        assert_eq!(remap[ten], Symbol::new(2));
+    }
+}
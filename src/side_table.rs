@@ -0,0 +1,248 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::any::{Any, TypeId};
+use core::fmt;
+use core::hash::BuildHasher;
+use core::marker::PhantomData;
+
+use crate::{Backend, Key, Lexicon, Symbol};
+
+/// Value of type `T` for every key of a `Lexicon`, e.g., flags classifying
+/// each identifier. Created by `Lexicon::side_table`, after which the
+/// `Lexicon` keeps it in sync with its keys: every new key is given
+/// `T::default()`, while the values of keys discarded by `truncate_to`,
+/// `clear` or `retain` are dropped along with them.
+#[derive(Clone, Debug)]
+pub struct SideTable<T, K = Symbol> {
+    values: Vec<T>,
+    key: PhantomData<K>,
+}
+
+impl<T, K: Key> SideTable<T, K> {
+    pub fn get(&self, key: K) -> Option<&T> {
+        self.values.get(key.to_usize())
+    }
+
+    pub fn get_mut(&mut self, key: K) -> Option<&mut T> {
+        self.values.get_mut(key.to_usize())
+    }
+
+    /// Returns every key along with its value, in order of key.
+    pub fn iter(&self) -> impl Iterator<Item = (K, &T)> + '_ {
+        self.values.iter().enumerate().map(|(idx, value)| {
+            let key = K::try_from_usize(idx).expect("side table holds an invalid key");
+            (key, value)
+        })
+    }
+
+    /// Returns the values of every key, indexed by key.
+    pub fn as_slice(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+
+    /// Returns the number of keys, i.e., the length of the `Lexicon`.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<T, K: Key> core::ops::Index<K> for SideTable<T, K> {
+    type Output = T;
+
+    fn index(&self, key: K) -> &Self::Output {
+        match self.get(key) {
+            Some(value) => value,
+            None => panic!("key `{:?}` is not part of the side table", key),
+        }
+    }
+}
+
+impl<T, K: Key> core::ops::IndexMut<K> for SideTable<T, K> {
+    fn index_mut(&mut self, key: K) -> &mut Self::Output {
+        match self.get_mut(key) {
+            Some(value) => value,
+            None => panic!("key `{:?}` is not part of the side table", key),
+        }
+    }
+}
+
+/// A `SideTable` of any value type, as kept by a `Lexicon`.
+trait Column: Send + Sync {
+    fn push_default(&mut self);
+    fn truncate(&mut self, len: usize);
+    /// Keeps the values of the keys `kept` holds `true` for, in order.
+    fn retain(&mut self, kept: &[bool]);
+    fn shrink_to_fit(&mut self);
+    fn clone_box(&self) -> Box<dyn Column>;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T, K> Column for SideTable<T, K>
+where
+    T: Default + Clone + Send + Sync + 'static,
+    K: Key + Send + Sync + 'static,
+{
+    fn push_default(&mut self) {
+        self.values.push(T::default());
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.values.truncate(len);
+    }
+
+    fn retain(&mut self, kept: &[bool]) {
+        let mut kept = kept.iter();
+        self.values.retain(|_| kept.next() == Some(&true));
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.values.shrink_to_fit();
+    }
+
+    fn clone_box(&self) -> Box<dyn Column> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Side tables registered on a `Lexicon`, along with the type of each.
+#[derive(Default)]
+pub(crate) struct SideTables {
+    columns: Vec<(TypeId, Box<dyn Column>)>,
+}
+
+impl SideTables {
+    pub fn push_default(&mut self) {
+        for (_, column) in &mut self.columns {
+            column.push_default();
+        }
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        for (_, column) in &mut self.columns {
+            column.truncate(len);
+        }
+    }
+
+    pub fn retain(&mut self, kept: &[bool]) {
+        for (_, column) in &mut self.columns {
+            column.retain(kept);
+        }
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        for (_, column) in &mut self.columns {
+            column.shrink_to_fit();
+        }
+        self.columns.shrink_to_fit();
+    }
+}
+
+impl Clone for SideTables {
+    fn clone(&self) -> Self {
+        let columns = self.columns.iter();
+        Self {
+            columns: columns
+                .map(|(ty, column)| (*ty, column.clone_box()))
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Debug for SideTables {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SideTables({})", self.columns.len())
+    }
+}
+
+impl<K, H, B> Lexicon<K, H, B>
+where
+    K: Key + Send + Sync + 'static,
+    H: BuildHasher,
+    B: Backend,
+{
+    /// Returns the side table of values of type `T` (see `SideTable`),
+    /// registering it first, with `T::default()` for every key, if this
+    /// `Lexicon` has none yet. There is at most one side table per type, so
+    /// distinct kinds of metadata are best given distinct types, e.g.,
+    /// `lexicon.side_table::<Flags>()[id] |= Flags::KEYWORD`.
+    pub fn side_table<T>(&mut self) -> &mut SideTable<T, K>
+    where
+        T: Default + Clone + Send + Sync + 'static,
+    {
+        let ty = TypeId::of::<T>();
+        let columns = &mut self.side_tables.columns;
+        let idx = match columns.iter().position(|&(other, _)| other == ty) {
+            Some(idx) => idx,
+            None => {
+                let mut values = Vec::new();
+                values.resize_with(self.vec.len(), T::default);
+                let table = SideTable::<T, K> {
+                    values,
+                    key: PhantomData,
+                };
+                columns.push((ty, Box::new(table)));
+                columns.len() - 1
+            }
+        };
+        let column = columns[idx].1.as_any_mut();
+        column.downcast_mut().expect("side table of another type")
+    }
+
+    /// Returns the side table of values of type `T`, or `None` if it was
+    /// never registered through `side_table`.
+    pub fn get_side_table<T: 'static>(&self) -> Option<&SideTable<T, K>> {
+        let ty = TypeId::of::<T>();
+        let columns = &self.side_tables.columns;
+        let (_, column) = columns.iter().find(|&&(other, _)| other == ty)?;
+        column.as_any().downcast_ref()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    struct Flags(u8);
+
+    #[test]
+    fn test_side_table() {
+        let mut lexicon = Lexicon::<Symbol>::default();
+        let a = lexicon.intern("a");
+        lexicon.side_table::<Flags>()[a] = Flags(1);
+        let b = lexicon.intern("b");
+        assert_eq!(lexicon.side_table::<Flags>()[b], Flags(0));
+        *lexicon.side_table::<u32>().get_mut(b).unwrap() = 7;
+
+        let checkpoint = lexicon.checkpoint();
+        lexicon.intern("c");
+        assert_eq!(lexicon.get_side_table::<Flags>().unwrap().len(), 3);
+        lexicon.truncate_to(checkpoint);
+        assert_eq!(lexicon.get_side_table::<u32>().unwrap().as_slice(), [0, 7]);
+        assert!(lexicon.get_side_table::<u64>().is_none());
+
+        lexicon.retain(|_, string| string != "a");
+        let table = lexicon.get_side_table::<u32>().unwrap();
+        assert_eq!(table.iter().collect::<Vec<_>>(), [(Symbol::new(0), &7)]);
+        assert_eq!(lexicon.clone().side_table::<Flags>().as_slice(), [Flags(0)]);
+        lexicon.clear();
+        assert!(lexicon.side_table::<u32>().is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+
+/// Number of buckets needed to address every `u32` index: bucket `b` holds
+/// `2^b` slots, so buckets `0..=32` together cover `2^33 - 1` slots.
+const BUCKETS: usize = 33;
+
+/// Lock-free, append-only table of string slices indexed by `usize`.
+///
+/// Slots live in buckets of doubling size which are allocated on demand and
+/// never moved or freed before the table is dropped, so publishing into or
+/// reading from a slot never requires a lock and never invalidates
+/// references obtained by other threads.
+pub(crate) struct Slots {
+    buckets: [AtomicPtr<Slot>; BUCKETS],
+}
+
+struct Slot {
+    ptr: AtomicPtr<u8>,
+    len: AtomicUsize,
+}
+
+impl Slots {
+    pub fn new() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicPtr::new(ptr::null_mut())),
+        }
+    }
+
+    /// Returns the bucket and the offset within the bucket of index `idx`.
+    fn locate(idx: usize) -> (usize, usize) {
+        let pos = idx + 1;
+        let bucket = (usize::BITS - 1 - pos.leading_zeros()) as usize;
+        (bucket, pos - (1 << bucket))
+    }
+
+    fn bucket(&self, bucket: usize) -> *mut Slot {
+        let ptr = self.buckets[bucket].load(Ordering::Acquire);
+        if !ptr.is_null() {
+            return ptr;
+        }
+
+        let slots = (0..1usize << bucket)
+            .map(|_| Slot {
+                ptr: AtomicPtr::new(ptr::null_mut()),
+                len: AtomicUsize::new(0),
+            })
+            .collect::<Box<[_]>>();
+        let new = Box::into_raw(slots) as *mut Slot;
+        match self.buckets[bucket].compare_exchange(
+            ptr::null_mut(),
+            new,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => new,
+            Err(existing) => {
+                // another thread beat us to it; discard our allocation
+                drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(new, 1 << bucket)) });
+                existing
+            }
+        }
+    }
+
+    /// Publishes `string` into the slot at index `idx`. Each slot is expected
+    /// to only ever be published to once.
+    pub fn publish(&self, idx: usize, string: &'static str) {
+        let (bucket, offset) = Self::locate(idx);
+        let slot = unsafe { &*self.bucket(bucket).add(offset) };
+        slot.len.store(string.len(), Ordering::Relaxed);
+        slot.ptr
+            .store(string.as_ptr() as *mut u8, Ordering::Release);
+    }
+
+    /// Returns the string published at index `idx`, if any.
+    pub fn get(&self, idx: usize) -> Option<&'static str> {
+        let (bucket, offset) = Self::locate(idx);
+        let ptr = self.buckets.get(bucket)?.load(Ordering::Acquire);
+        if ptr.is_null() {
+            return None;
+        }
+        let slot = unsafe { &*ptr.add(offset) };
+        let data = slot.ptr.load(Ordering::Acquire);
+        if data.is_null() {
+            return None;
+        }
+        let len = slot.len.load(Ordering::Relaxed);
+        // the slot's contents were published from a valid `&'static str`
+        unsafe {
+            Some(std::str::from_utf8_unchecked(std::slice::from_raw_parts(
+                data, len,
+            )))
+        }
+    }
+}
+
+impl Drop for Slots {
+    fn drop(&mut self) {
+        for (bucket, ptr) in self.buckets.iter_mut().enumerate() {
+            let ptr = *ptr.get_mut();
+            if !ptr.is_null() {
+                drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(ptr, 1 << bucket)) });
+            }
+        }
+    }
+}
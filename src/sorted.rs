@@ -0,0 +1,305 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::BuildHasher;
+use core::marker::PhantomData;
+use core::ptr;
+use core::sync::atomic::{self, AtomicPtr};
+
+use crate::{Backend, Key, Lexicon, Symbol};
+
+/// Keys of a `Lexicon` in lexicographic order of their strings, answering
+/// rank and select queries in constant time. Returned by
+/// `Lexicon::sorted_index`, which builds it on first use and caches it until
+/// the `Lexicon` changes; clone it to keep a snapshot around.
+#[derive(Clone, Debug)]
+pub struct SortedIndex<K = Symbol> {
+    sorted: Vec<K>,
+    /// Position of every key within `sorted`, indexed by key, or `NONE` for
+    /// keys that were reserved but not defined.
+    ranks: Vec<u32>,
+}
+
+const NONE: u32 = u32::MAX;
+
+impl<K: Key> SortedIndex<K> {
+    /// Returns the position of the string of `id` in lexicographic order,
+    /// or `None` if `id` is not part of this index.
+    pub fn rank(&self, id: K) -> Option<usize> {
+        match self.ranks.get(id.to_usize()) {
+            Some(&NONE) | None => None,
+            Some(&rank) => Some(rank as usize),
+        }
+    }
+
+    /// Compares the strings of `a` and `b` through their rank alone, i.e.,
+    /// in constant time, without resolving either (e.g., as the comparator
+    /// for sorting many symbols).
+    ///
+    /// # Panics
+    /// Panics if `a` or `b` is not part of this index.
+    pub fn cmp_strs(&self, a: K, b: K) -> Ordering {
+        let rank = |id: K| match self.rank(id) {
+            Some(rank) => rank,
+            None => panic!("symbol `{}` is not part of the index", id.to_usize()),
+        };
+        rank(a).cmp(&rank(b))
+    }
+
+    /// Returns the key of the `rank`-th string in lexicographic order.
+    pub fn select(&self, rank: usize) -> Option<K> {
+        self.sorted.get(rank).copied()
+    }
+
+    /// Binary searches the index for `string` within `lexicon` (which this
+    /// index must have been created from), returning its rank if it is
+    /// interned, or the rank it would be inserted at otherwise.
+    pub fn binary_search<H: BuildHasher, B: Backend>(
+        &self,
+        lexicon: &Lexicon<K, H, B>,
+        string: &str,
+    ) -> Result<usize, usize> {
+        self.sorted
+            .binary_search_by(|&id| lexicon.lookup(id).cmp(string))
+    }
+
+    /// Returns the keys in lexicographic order of their strings.
+    pub fn as_slice(&self) -> &[K] {
+        &self.sorted
+    }
+
+    pub fn len(&self) -> usize {
+        self.sorted.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sorted.is_empty()
+    }
+}
+
+/// `SortedIndex` of a `Lexicon`, built on first use (possibly through a
+/// shared reference, and thus from several threads at once) and dropped
+/// whenever the strings of the `Lexicon` change.
+pub(crate) struct SortedCache<K> {
+    index: AtomicPtr<SortedIndex<K>>,
+    key: PhantomData<Box<SortedIndex<K>>>,
+}
+
+// the index is built by whichever thread first asks for it, and dropped by
+// the owner of the `Lexicon`
+unsafe impl<K: Send + Sync> Sync for SortedCache<K> {}
+unsafe impl<K: Send> Send for SortedCache<K> {}
+
+impl<K> SortedCache<K> {
+    fn get_or_build(&self, build: impl FnOnce() -> SortedIndex<K>) -> &SortedIndex<K> {
+        let index = self.index.load(atomic::Ordering::Acquire);
+        if !index.is_null() {
+            // SAFETY: published indices are only freed through `&mut self`
+            return unsafe { &*index };
+        }
+        let new = Box::into_raw(Box::new(build()));
+        let published = self.index.compare_exchange(
+            ptr::null_mut(),
+            new,
+            atomic::Ordering::AcqRel,
+            atomic::Ordering::Acquire,
+        );
+        match published {
+            Ok(_) => unsafe { &*new },
+            Err(other) => {
+                // another thread built it first
+                drop(unsafe { Box::from_raw(new) });
+                unsafe { &*other }
+            }
+        }
+    }
+
+    pub fn invalidate(&mut self) {
+        let index = core::mem::replace(self.index.get_mut(), ptr::null_mut());
+        if !index.is_null() {
+            // SAFETY: the index was leaked by `get_or_build`, and no reference
+            // to it outlives the `&mut self` borrow
+            drop(unsafe { Box::from_raw(index) });
+        }
+    }
+
+    pub fn allocation_size(&self) -> usize {
+        // SAFETY: see `get_or_build`
+        match unsafe { self.index.load(atomic::Ordering::Acquire).as_ref() } {
+            Some(index) => {
+                core::mem::size_of::<SortedIndex<K>>()
+                    + index.sorted.capacity() * core::mem::size_of::<K>()
+                    + index.ranks.capacity() * core::mem::size_of::<u32>()
+            }
+            None => 0,
+        }
+    }
+}
+
+impl<K> Default for SortedCache<K> {
+    fn default() -> Self {
+        Self {
+            index: AtomicPtr::new(ptr::null_mut()),
+            key: PhantomData,
+        }
+    }
+}
+
+/// Clones start out without an index, building their own on first use.
+impl<K> Clone for SortedCache<K> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl<K> Drop for SortedCache<K> {
+    fn drop(&mut self) {
+        self.invalidate();
+    }
+}
+
+impl<K> fmt::Debug for SortedCache<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let built = !self.index.load(atomic::Ordering::Relaxed).is_null();
+        write!(f, "SortedCache({})", if built { "built" } else { "empty" })
+    }
+}
+
+impl<K: Key, H: BuildHasher, B: Backend> Lexicon<K, H, B> {
+    /// Returns the `SortedIndex` of the strings currently interned, e.g., to
+    /// present them alphabetically, or to binary search them. The index is
+    /// built on first use, taking linear time if the trie of
+    /// `index_prefixes` is maintained and sorting every string otherwise,
+    /// then reused until a string is interned, defined or removed.
+    pub fn sorted_index(&self) -> &SortedIndex<K> {
+        self.sorted.get_or_build(|| {
+            let sorted = self.sorted_keys();
+            let mut ranks = alloc::vec![NONE; self.vec.len()];
+            for (rank, id) in sorted.iter().enumerate() {
+                ranks[id.to_usize()] = rank as u32;
+            }
+            SortedIndex { sorted, ranks }
+        })
+    }
+
+    /// Returns the position of the string of `id` in lexicographic order
+    /// among the strings of this `Lexicon`, or `None` if `id` was not
+    /// interned in it (or is reserved). See `sorted_index`.
+    pub fn rank(&self, id: K) -> Option<usize> {
+        self.sorted_index().rank(id)
+    }
+
+    /// Returns the key of the `rank`-th string in lexicographic order. See
+    /// `sorted_index`.
+    pub fn select(&self, rank: usize) -> Option<K> {
+        self.sorted_index().select(rank)
+    }
+
+    /// Compares the strings of `a` and `b`, without resolving them if `a`
+    /// and `b` are the same key. See `SortedIndex::cmp_strs` for comparing
+    /// many keys without resolving them at all.
+    pub fn cmp_strs(&self, a: K, b: K) -> Ordering {
+        if a == b {
+            return Ordering::Equal;
+        }
+        self.lookup(a).cmp(self.lookup(b))
+    }
+
+    /// Sorts `ids` by their strings, resolving every key only once rather
+    /// than at every comparison.
+    pub fn sort_symbols(&self, ids: &mut [K]) {
+        ids.sort_by_cached_key(|&id| self.lookup(id));
+    }
+
+    /// Returns an iterator over every interned string along with its key,
+    /// in lexicographic order, as listed by `sorted_index`.
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (K, &str)> + '_ {
+        let sorted = self.sorted_index().as_slice().iter();
+        sorted.map(move |&id| (id, self.lookup(id)))
+    }
+
+    fn sorted_keys(&self) -> Vec<K> {
+        if self.prefixes.is_some() {
+            return self.symbols_with_prefix("").map(|(id, _)| id).collect();
+        }
+        let mut sorted = (0..self.vec.len())
+            .filter(|idx| !self.pending.contains(idx))
+            .map(|idx| K::try_from_usize(idx).expect("interned string has no key"))
+            .collect::<Vec<_>>();
+        sorted.sort_unstable_by(|&a, &b| self.lookup(a).cmp(self.lookup(b)));
+        sorted
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sorted_index() {
+        let mut lexicon = Lexicon::default();
+        let ids = lexicon.intern_all(["pear", "apple", "fig"]);
+        let reserved = lexicon.reserve_symbol();
+        for indexed in [false, true] {
+            if indexed {
+                lexicon.index_prefixes();
+            }
+            let index = lexicon.sorted_index();
+            assert_eq!(index.as_slice(), [ids[1], ids[2], ids[0]]);
+            assert_eq!(index.rank(ids[0]), Some(2));
+            assert_eq!(index.rank(reserved), None);
+            assert_eq!(index.select(0), Some(ids[1]));
+            assert_eq!(index.binary_search(&lexicon, "fig"), Ok(1));
+            assert_eq!(index.binary_search(&lexicon, "kiwi"), Err(2));
+
+            let sorted = lexicon.iter_sorted().map(|(_, s)| s).collect::<Vec<_>>();
+            assert_eq!(sorted, ["apple", "fig", "pear"]);
+        }
+    }
+
+    #[test]
+    fn test_sorted_cache() {
+        let mut lexicon = Lexicon::default();
+        let ids = lexicon.intern_all(["b", "d"]);
+        let reserved = lexicon.reserve_symbol();
+        assert_eq!(lexicon.rank(ids[1]), Some(1));
+        assert!(core::ptr::eq(
+            lexicon.sorted_index(),
+            lexicon.sorted_index()
+        ));
+
+        let c = lexicon.intern("c");
+        assert_eq!(lexicon.rank(ids[1]), Some(2));
+        assert_eq!(lexicon.select(1), Some(c));
+        lexicon.define(reserved, "a");
+        assert_eq!(lexicon.select(0), Some(reserved));
+
+        let checkpoint = lexicon.checkpoint();
+        lexicon.intern("0");
+        assert_eq!(lexicon.rank(ids[0]), Some(2));
+        lexicon.truncate_to(checkpoint);
+        assert_eq!(lexicon.rank(ids[0]), Some(1));
+
+        let remap = lexicon.retain(|_, s| s != "b");
+        assert_eq!(lexicon.rank(remap[ids[1]]), Some(2));
+        assert_eq!(lexicon.clone().select(2), Some(remap[ids[1]]));
+        lexicon.clear();
+        assert_eq!(lexicon.select(0), None);
+    }
+
+    #[test]
+    fn test_cmp_strs() {
+        let mut lexicon = Lexicon::default();
+        let mut ids = lexicon.intern_all(["b", "c", "a", "b"]);
+        assert_eq!(lexicon.cmp_strs(ids[0], ids[1]), Ordering::Less);
+        assert_eq!(lexicon.cmp_strs(ids[0], ids[3]), Ordering::Equal);
+
+        let index = lexicon.sorted_index();
+        let mut by_rank = ids.clone();
+        by_rank.sort_by(|&a, &b| index.cmp_strs(a, b));
+        lexicon.sort_symbols(&mut ids);
+        assert_eq!(ids, by_rank);
+        assert_eq!(lexicon.join(&ids, ""), "abbc");
+    }
+}
@@ -0,0 +1,162 @@
+use core::fmt;
+use core::num::NonZeroU64;
+
+use hashbrown::HashMap;
+
+use crate::arena::{Arena, Span};
+use crate::{Backend, Lexicon};
+
+/// Key of a `StableLexicon`, derived from the contents of its string rather
+/// than from the order strings were interned in.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct StableSymbol(NonZeroU64);
+
+impl StableSymbol {
+    /// Returns the key `string` is given by every `StableLexicon`, unless it
+    /// collides with another string (see `StableLexicon`).
+    ///
+    /// Computed as the 64-bit FNV-1a hash of the UTF-8 bytes of `string`
+    /// (with `0` taken as `1`), which is part of the stability guarantee and
+    /// thus never changes.
+    pub const fn hash_of(string: &str) -> Self {
+        let bytes = string.as_bytes();
+        let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+        let mut i = 0;
+        while i < bytes.len() {
+            hash ^= bytes[i] as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+            i += 1;
+        }
+        match NonZeroU64::new(hash) {
+            Some(hash) => Self(hash),
+            None => Self(NonZeroU64::MIN),
+        }
+    }
+
+    pub fn as_u64(&self) -> u64 {
+        self.0.get()
+    }
+
+    /// Returns the key following this one, where a colliding string is
+    /// probed for next.
+    fn next(self) -> Self {
+        Self(NonZeroU64::new(self.0.get().wrapping_add(1)).unwrap_or(NonZeroU64::MIN))
+    }
+}
+
+impl fmt::Debug for StableSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "StableSymbol({:#018x})", self.as_u64())
+    }
+}
+
+/// String interner whose keys are derived from a stable hash of each
+/// string's contents (see `StableSymbol::hash_of`), so a string is given
+/// the same key in every process and run, e.g., for distributed build
+/// caches.
+///
+/// Should two strings hash to the same key, the one interned later is given
+/// the next free key instead. Such collisions are astronomically unlikely,
+/// but their keys do depend on the order the strings were interned in.
+#[derive(Clone, Debug)]
+pub struct StableLexicon {
+    map: HashMap<StableSymbol, Span>,
+    arena: Arena,
+}
+
+impl StableLexicon {
+    pub fn new() -> Self {
+        Self {
+            map: HashMap::new(),
+            arena: Arena::with_capacity(Lexicon::BASE_CAPACITY),
+        }
+    }
+
+    pub fn intern(&mut self, string: &str) -> StableSymbol {
+        let mut id = StableSymbol::hash_of(string);
+        loop {
+            match self.map.get(&id) {
+                Some(&span) if self.arena.get(span) == string => return id,
+                Some(_) => id = id.next(),
+                None => break,
+            }
+        }
+        self.map.insert(id, self.arena.alloc(string));
+        id
+    }
+
+    pub fn get(&self, string: &str) -> Option<StableSymbol> {
+        let mut id = StableSymbol::hash_of(string);
+        loop {
+            match self.map.get(&id) {
+                Some(&span) if self.arena.get(span) == string => return Some(id),
+                Some(_) => id = id.next(),
+                None => return None,
+            }
+        }
+    }
+
+    pub fn lookup(&self, id: StableSymbol) -> &str {
+        match self.try_lookup(id) {
+            Some(string) => string,
+            None => panic!("symbol `{:?}` was not interned", id),
+        }
+    }
+
+    pub fn try_lookup(&self, id: StableSymbol) -> Option<&str> {
+        self.map.get(&id).map(|&span| self.arena.get(span))
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl Default for StableLexicon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::ops::Index<StableSymbol> for StableLexicon {
+    type Output = str;
+
+    fn index(&self, index: StableSymbol) -> &Self::Output {
+        self.lookup(index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stable_ids() {
+        let mut a = StableLexicon::new();
+        let mut b = StableLexicon::new();
+        let foo = a.intern("foo");
+        a.intern("bar");
+        assert_eq!(b.intern("bar"), a.get("bar").unwrap());
+        assert_eq!(b.intern("foo"), foo);
+        assert_eq!(foo.as_u64(), 0xdcb2_7518_fed9_d577);
+        assert_eq!(&a[foo], "foo");
+        assert_eq!(a.get("baz"), None);
+    }
+
+    #[test]
+    fn test_collisions() {
+        let mut lexicon = StableLexicon::new();
+        // occupy the key of "b" with another string
+        let span = lexicon.arena.alloc("a");
+        lexicon.map.insert(StableSymbol::hash_of("b"), span);
+        let b = lexicon.intern("b");
+        assert_eq!(b, StableSymbol::hash_of("b").next());
+        assert_eq!(lexicon.intern("b"), b);
+        assert_eq!(lexicon.get("b"), Some(b));
+        assert_eq!(lexicon.lookup(b), "b");
+    }
+}
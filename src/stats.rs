@@ -0,0 +1,62 @@
+use core::hash::BuildHasher;
+
+use crate::{Backend, Key, Lexicon};
+
+/// Counters of the work done by a `Lexicon` while interning, e.g., for
+/// tuning its initial capacity, or checking how many strings preloading
+/// saves storing later. Created by `Lexicon::stats`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InternStats {
+    /// Number of strings interned which were already present.
+    pub hits: u64,
+    /// Number of strings interned which were new, and thus stored.
+    pub misses: u64,
+    /// Total length of the strings stored by misses.
+    pub bytes_allocated: u64,
+    /// Number of times the table mapping strings to their keys grew,
+    /// rehashing every string in it.
+    pub rehashes: u64,
+    /// Number of times the table mapping keys to their strings grew.
+    pub vec_growths: u64,
+}
+
+impl<K: Key, H: BuildHasher, B: Backend> Lexicon<K, H, B> {
+    /// Returns the counters of the work done while interning since this
+    /// `Lexicon` was created (or since `reset_stats`). Clearing or
+    /// truncating the `Lexicon` leaves them as they are.
+    pub fn stats(&self) -> InternStats {
+        self.stats
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.stats = InternStats::default();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Symbol;
+
+    #[test]
+    fn test_stats() {
+        let mut lexicon = Lexicon::<Symbol>::with_capacity(0);
+        lexicon.intern("a");
+        lexicon.intern("bc");
+        lexicon.intern("a");
+        let stats = lexicon.stats();
+        assert_eq!((stats.hits, stats.misses, stats.bytes_allocated), (1, 2, 3));
+        assert!(stats.rehashes >= 1 && stats.vec_growths >= 1);
+
+        lexicon.reset_stats();
+        lexicon.reserve(100, 0);
+        for i in 0..100 {
+            lexicon.intern_fmt(format_args!("{}", i));
+        }
+        let stats = lexicon.stats();
+        assert_eq!(
+            (stats.misses, stats.rehashes, stats.vec_growths),
+            (100, 0, 0)
+        );
+    }
+}
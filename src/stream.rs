@@ -0,0 +1,96 @@
+use core::hash::BuildHasher;
+use std::io::{self, BufRead};
+use std::str;
+
+use crate::{Backend, Key, Lexicon};
+
+impl<K: Key, H: BuildHasher, B: Backend> Lexicon<K, H, B> {
+    /// Interns every line read from `reader` (e.g., a word list), returning
+    /// their keys in order. Lines are split as by `BufRead::lines`, i.e.,
+    /// without their `\n` or `\r\n` terminator, but are read into a single
+    /// buffer reused from one line to the next, rather than each allocating
+    /// its own `String`.
+    ///
+    /// Fails if reading fails, if a line is not valid UTF-8
+    /// (`io::ErrorKind::InvalidData`), or if interning fails (see
+    /// `try_intern`), keeping the lines interned so far.
+    pub fn intern_lines(&mut self, reader: impl BufRead) -> io::Result<Vec<K>> {
+        let mut ids = Vec::new();
+        self.for_each_line(reader, |lexicon, line| {
+            ids.push(lexicon.try_intern(line).map_err(io::Error::other)?);
+            Ok(())
+        })?;
+        Ok(ids)
+    }
+
+    /// Like `intern_lines`, but interns every whitespace-separated token of
+    /// each line instead (e.g., the vocabulary of a log), as split by
+    /// `str::split_whitespace`.
+    pub fn intern_tokens(&mut self, reader: impl BufRead) -> io::Result<Vec<K>> {
+        let mut ids = Vec::new();
+        self.for_each_line(reader, |lexicon, line| {
+            for token in line.split_whitespace() {
+                ids.push(lexicon.try_intern(token).map_err(io::Error::other)?);
+            }
+            Ok(())
+        })?;
+        Ok(ids)
+    }
+
+    /// Calls `f` with every line of `reader`, stripped of its terminator.
+    fn for_each_line(
+        &mut self,
+        mut reader: impl BufRead,
+        mut f: impl FnMut(&mut Self, &str) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let mut buf = Vec::new();
+        while reader.read_until(b'\n', &mut buf)? != 0 {
+            let mut line = &buf[..];
+            if let Some(rest) = line.strip_suffix(b"\n") {
+                line = rest.strip_suffix(b"\r").unwrap_or(rest);
+            }
+            let line =
+                str::from_utf8(line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            f(self, line)?;
+            buf.clear();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{LexiconError, Symbol};
+
+    #[test]
+    fn test_intern_lines() {
+        let mut lexicon = Lexicon::<Symbol>::default();
+        let ids = lexicon
+            .intern_lines(&b"apple\r\nbanana\n\napple"[..])
+            .unwrap();
+        let lines = ids.iter().map(|&id| lexicon.lookup(id)).collect::<Vec<_>>();
+        assert_eq!(lines, ["apple", "banana", "", "apple"]);
+        assert_eq!(ids[0], ids[3]);
+
+        let ids = lexicon
+            .intern_tokens(&b"  GET /index\tGET\n\n /a \n"[..])
+            .unwrap();
+        let tokens = ids.iter().map(|&id| lexicon.lookup(id)).collect::<Vec<_>>();
+        assert_eq!(tokens, ["GET", "/index", "GET", "/a"]);
+
+        let len = lexicon.len();
+        let e = lexicon.intern_lines(&b"new\n\xff\n"[..]).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(lexicon.len(), len + 1);
+    }
+
+    #[test]
+    fn test_intern_lines_error() {
+        let mut lexicon = Lexicon::<Symbol>::default();
+        lexicon.set_memory_limit(Some(1));
+        let e = lexicon.intern_tokens(&b"a b"[..]).unwrap_err();
+        let e = e.into_inner().unwrap().downcast::<LexiconError>().unwrap();
+        assert_eq!(*e, LexiconError::MemoryLimitExceeded);
+    }
+}
@@ -0,0 +1,78 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use crate::{Backend, Key, Lexicon};
+
+impl<K: Key, H: BuildHasher, B: Backend> Lexicon<K, H, B> {
+    /// Returns every interned string within `max_distance` edits (insertions,
+    /// deletions or substitutions of a single `char`) of `query`, along with
+    /// its distance, closest first, e.g., for "did you mean" diagnostics.
+    /// Strings at the same distance are listed in the order they were
+    /// interned. `query` itself is included (at distance 0) if interned.
+    ///
+    /// Every string is scanned, though most are dismissed early, either by
+    /// their length or once they are known to be too far off.
+    pub fn suggest(&self, query: &str, max_distance: usize) -> Vec<(K, u32)> {
+        let query = query.chars().collect::<Vec<_>>();
+        let mut row = Vec::with_capacity(query.len() + 1);
+        let mut suggestions = Vec::new();
+        for (idx, &span) in self.vec.iter().enumerate() {
+            if self.pending.contains(&idx) {
+                continue;
+            }
+            let string = self.backend.get(span);
+            // a string takes at least as many edits as it differs in length
+            let len = string.chars().count();
+            if len.abs_diff(query.len()) > max_distance {
+                continue;
+            }
+            if let Some(distance) = distance(&query, string, max_distance, &mut row) {
+                let id = K::try_from_usize(idx).expect("interned string has no key");
+                suggestions.push((id, distance as u32));
+            }
+        }
+        suggestions.sort_by_key(|&(id, distance)| (distance, id.to_usize()));
+        suggestions
+    }
+}
+
+/// Returns the edit distance between `query` and `string` if it is at most
+/// `max`, using `row` as scratch space.
+fn distance(query: &[char], string: &str, max: usize, row: &mut Vec<usize>) -> Option<usize> {
+    // `row[j]` is the distance between the part of `string` seen so far and
+    // the first `j` chars of `query`
+    row.clear();
+    row.extend(0..=query.len());
+    for (i, c) in string.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        let mut min = row[0];
+        for (j, &q) in query.iter().enumerate() {
+            let substitution = diagonal + usize::from(c != q);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+            min = min.min(row[j + 1]);
+        }
+        // distances never decrease from one row to the next
+        if min > max {
+            return None;
+        }
+    }
+    Some(row[query.len()]).filter(|&distance| distance <= max)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_suggest() {
+        let mut lexicon = Lexicon::default();
+        let ids = lexicon.intern_all(["length", "lenght", "len", "width", "strength"]);
+        assert_eq!(lexicon.suggest("lenght", 2), [(ids[1], 0), (ids[0], 2)]);
+        assert_eq!(lexicon.suggest("widht", 1), []);
+        assert_eq!(lexicon.suggest("widht", 2), [(ids[3], 2)]);
+        assert_eq!(lexicon.suggest("ln", 1), [(ids[2], 1)]);
+        assert_eq!(lexicon.suggest("", 3), [(ids[2], 3)]);
+    }
+}
@@ -0,0 +1,272 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+
+use crate::{Key, Symbol};
+
+/// Map from keys to values of type `V`, stored densely in a table indexed
+/// by key, e.g., for attaching types or doc strings to every symbol of a
+/// `Lexicon`. Cheaper than a `HashMap` keyed by symbol, as no key is ever
+/// hashed, provided most keys up to the largest one present have a value.
+#[derive(Clone)]
+pub struct SymbolMap<V, K = Symbol> {
+    values: Vec<Option<V>>,
+    /// Number of `Some` values.
+    len: usize,
+    key: PhantomData<K>,
+}
+
+impl<V, K: Key> SymbolMap<V, K> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates an empty map with room for the keys of `cap` strings, e.g.,
+    /// `SymbolMap::with_capacity(lexicon.len())`.
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            values: Vec::with_capacity(cap),
+            len: 0,
+            key: PhantomData,
+        }
+    }
+
+    /// Sets the value of `key`, returning its previous value, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let old = self.slot(key).replace(value);
+        self.len += old.is_none() as usize;
+        old
+    }
+
+    pub fn get(&self, key: K) -> Option<&V> {
+        self.values.get(key.to_usize())?.as_ref()
+    }
+
+    pub fn get_mut(&mut self, key: K) -> Option<&mut V> {
+        self.values.get_mut(key.to_usize())?.as_mut()
+    }
+
+    pub fn contains_key(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes the value of `key`, returning it, if any.
+    pub fn remove(&mut self, key: K) -> Option<V> {
+        let value = self.values.get_mut(key.to_usize())?.take();
+        self.len -= value.is_some() as usize;
+        value
+    }
+
+    /// Returns the entry of `key`, for inserting or updating its value in
+    /// place.
+    pub fn entry(&mut self, key: K) -> Entry<'_, V, K> {
+        Entry { map: self, key }
+    }
+
+    /// Returns the number of keys with a value.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.len = 0;
+    }
+
+    /// Returns every key along with its value, in order of key.
+    pub fn iter(&self) -> impl Iterator<Item = (K, &V)> + '_ {
+        let values = self.values.iter().enumerate();
+        values.filter_map(|(idx, value)| Some((key_of(idx), value.as_ref()?)))
+    }
+
+    /// Like `iter`, but with mutable references to the values.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (K, &mut V)> + '_ {
+        let values = self.values.iter_mut().enumerate();
+        values.filter_map(|(idx, value)| Some((key_of(idx), value.as_mut()?)))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.values.iter().flatten()
+    }
+
+    /// Returns the slot of `key`, growing the table up to it if needed.
+    fn slot(&mut self, key: K) -> &mut Option<V> {
+        let idx = key.to_usize();
+        if idx >= self.values.len() {
+            self.values.resize_with(idx + 1, || None);
+        }
+        &mut self.values[idx]
+    }
+}
+
+/// Every index within the table was derived from a key in the first place.
+fn key_of<K: Key>(idx: usize) -> K {
+    K::try_from_usize(idx).expect("symbol map holds an invalid key")
+}
+
+/// Entry of a single key of a `SymbolMap`, created by `SymbolMap::entry`.
+pub struct Entry<'a, V, K = Symbol> {
+    map: &'a mut SymbolMap<V, K>,
+    key: K,
+}
+
+impl<'a, V, K: Key> Entry<'a, V, K> {
+    pub fn key(&self) -> K {
+        self.key
+    }
+
+    /// Returns the value of the key, inserting `default` first if it has
+    /// none.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Returns the value of the key, inserting the result of `default` first
+    /// if it has none.
+    pub fn or_insert_with(self, default: impl FnOnce() -> V) -> &'a mut V {
+        let Entry { map, key } = self;
+        let slot = map.slot(key);
+        if slot.is_none() {
+            *slot = Some(default());
+            map.len += 1;
+        }
+        map.values[key.to_usize()].as_mut().unwrap()
+    }
+
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Calls `f` with the value of the key, if it has one.
+    pub fn and_modify(self, f: impl FnOnce(&mut V)) -> Self {
+        if let Some(value) = self.map.get_mut(self.key) {
+            f(value);
+        }
+        self
+    }
+
+    /// Sets the value of the key, returning its previous value, if any.
+    pub fn insert(self, value: V) -> Option<V> {
+        self.map.insert(self.key, value)
+    }
+
+    /// Removes the value of the key, returning it, if any.
+    pub fn remove(self) -> Option<V> {
+        self.map.remove(self.key)
+    }
+}
+
+impl<V, K: Key> Default for SymbolMap<V, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Maps are equal if they hold the same values for the same keys,
+/// regardless of capacity.
+impl<V: PartialEq, K: Key> PartialEq for SymbolMap<V, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<V: Eq, K: Key> Eq for SymbolMap<V, K> {}
+
+impl<V: fmt::Debug, K: Key> fmt::Debug for SymbolMap<V, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<V, K: Key> core::ops::Index<K> for SymbolMap<V, K> {
+    type Output = V;
+
+    fn index(&self, key: K) -> &Self::Output {
+        match self.get(key) {
+            Some(value) => value,
+            None => panic!("key `{:?}` has no value", key),
+        }
+    }
+}
+
+impl<V, K: Key> core::ops::IndexMut<K> for SymbolMap<V, K> {
+    fn index_mut(&mut self, key: K) -> &mut Self::Output {
+        match self.get_mut(key) {
+            Some(value) => value,
+            None => panic!("key `{:?}` has no value", key),
+        }
+    }
+}
+
+impl<V, K: Key> Extend<(K, V)> for SymbolMap<V, K> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<V, K: Key> FromIterator<(K, V)> for SymbolMap<V, K> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_symbol_map() {
+        let (a, b, c) = (Symbol::new(0), Symbol::new(5), Symbol::new(2));
+        let mut map = SymbolMap::new();
+        assert_eq!(map.insert(b, "b"), None);
+        assert_eq!(map.insert(a, "a"), None);
+        assert_eq!(map.insert(a, "A"), Some("a"));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(c), None);
+        assert_eq!(map[b], "b");
+        assert_eq!(map.iter().collect::<Vec<_>>(), [(a, &"A"), (b, &"b")]);
+
+        assert_eq!(map.remove(b), Some("b"));
+        assert_eq!(map.remove(b), None);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.keys().collect::<Vec<_>>(), [a]);
+
+        map.remove(a);
+        assert_eq!(map, SymbolMap::new());
+        assert_ne!(map, [(c, "c")].iter().copied().collect());
+    }
+
+    #[test]
+    fn test_entry() {
+        let mut counts = SymbolMap::<u32>::new();
+        let (a, b) = (Symbol::new(1), Symbol::new(3));
+        for key in [a, b, a] {
+            *counts.entry(key).or_default() += 1;
+        }
+        counts
+            .entry(b)
+            .and_modify(|count| *count *= 10)
+            .or_insert(0);
+        assert_eq!((counts[a], counts[b]), (2, 10));
+        assert_eq!(counts.entry(a).remove(), Some(2));
+        assert_eq!(counts.entry(Symbol::new(7)).remove(), None);
+        assert_eq!(counts.len(), 1);
+        assert_eq!(format!("{:?}", counts).matches("10").count(), 1);
+    }
+}
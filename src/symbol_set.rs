@@ -0,0 +1,253 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+use core::ops::{BitAnd, BitOr, Sub};
+
+use crate::{Key, Symbol};
+
+const BITS: usize = u64::BITS as usize;
+
+/// Set of keys stored as a bitset, one bit per key, e.g., for liveness
+/// analyses over the identifiers of a `Lexicon`. Sized for the keys of a
+/// given number of strings up front (see `with_capacity`), and grown as
+/// larger keys are inserted.
+///
+/// The in-place set operations report whether they changed the set, as
+/// needed for iterating data-flow equations to a fixed point.
+#[derive(Clone)]
+pub struct SymbolSet<K = Symbol> {
+    words: Vec<u64>,
+    key: PhantomData<K>,
+}
+
+impl<K: Key> SymbolSet<K> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates an empty set with room for the keys of `cap` strings, e.g.,
+    /// `SymbolSet::with_capacity(lexicon.len())`.
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            words: alloc::vec![0; cap.div_ceil(BITS)],
+            key: PhantomData,
+        }
+    }
+
+    /// Adds `key`, returning whether it was newly added.
+    pub fn insert(&mut self, key: K) -> bool {
+        let (word, bit) = position(key);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        let new = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        new
+    }
+
+    /// Removes `key`, returning whether it was present.
+    pub fn remove(&mut self, key: K) -> bool {
+        let (word, bit) = position(key);
+        match self.words.get_mut(word) {
+            Some(word) => {
+                let present = *word & bit != 0;
+                *word &= !bit;
+                present
+            }
+            None => false,
+        }
+    }
+
+    pub fn contains(&self, key: K) -> bool {
+        let (word, bit) = position(key);
+        self.words.get(word).is_some_and(|word| word & bit != 0)
+    }
+
+    /// Returns the number of keys in the set.
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// Removes every key, keeping the set's capacity.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Returns the keys in the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = K> + '_ {
+        self.words.iter().enumerate().flat_map(|(idx, &word)| {
+            let mut rest = word;
+            core::iter::from_fn(move || {
+                let bit = rest.trailing_zeros() as usize;
+                rest &= rest.checked_sub(1)?;
+                Some(K::try_from_usize(idx * BITS + bit).expect("symbol set holds an invalid key"))
+            })
+        })
+    }
+
+    /// Adds every key of `other`, returning whether any was new.
+    pub fn union_with(&mut self, other: &Self) -> bool {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    /// Removes every key not in `other`, returning whether any was removed.
+    pub fn intersect_with(&mut self, other: &Self) -> bool {
+        let extra = self.words.get(other.words.len()..).unwrap_or_default();
+        let changed = extra.iter().any(|&word| word != 0);
+        self.words.truncate(other.words.len());
+        self.zip_with(other, |a, b| a & b) || changed
+    }
+
+    /// Removes every key of `other`, returning whether any was removed.
+    pub fn difference_with(&mut self, other: &Self) -> bool {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    /// Whether every key of this set is in `other` as well.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .enumerate()
+            .all(|(idx, &word)| word & !other.words.get(idx).copied().unwrap_or(0) == 0)
+    }
+
+    /// Combines each word of this set with the corresponding one of `other`
+    /// (while both have one), returning whether any word changed.
+    fn zip_with(&mut self, other: &Self, op: impl Fn(u64, u64) -> u64) -> bool {
+        let mut changed = false;
+        for (word, &other) in self.words.iter_mut().zip(&other.words) {
+            let new = op(*word, other);
+            changed |= new != *word;
+            *word = new;
+        }
+        changed
+    }
+}
+
+fn position<K: Key>(key: K) -> (usize, u64) {
+    let idx = key.to_usize();
+    (idx / BITS, 1 << (idx % BITS))
+}
+
+impl<K: Key> Default for SymbolSet<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sets are equal if they hold the same keys, regardless of capacity.
+impl<K: Key> PartialEq for SymbolSet<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_subset(other) && other.is_subset(self)
+    }
+}
+
+impl<K: Key> Eq for SymbolSet<K> {}
+
+impl<K: Key> fmt::Debug for SymbolSet<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Key> Extend<K> for SymbolSet<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.insert(key);
+        }
+    }
+}
+
+impl<K: Key> FromIterator<K> for SymbolSet<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<K: Key> BitOr for &SymbolSet<K> {
+    type Output = SymbolSet<K>;
+
+    fn bitor(self, other: Self) -> SymbolSet<K> {
+        let mut set = self.clone();
+        set.union_with(other);
+        set
+    }
+}
+
+impl<K: Key> BitAnd for &SymbolSet<K> {
+    type Output = SymbolSet<K>;
+
+    fn bitand(self, other: Self) -> SymbolSet<K> {
+        let mut set = self.clone();
+        set.intersect_with(other);
+        set
+    }
+}
+
+impl<K: Key> Sub for &SymbolSet<K> {
+    type Output = SymbolSet<K>;
+
+    fn sub(self, other: Self) -> SymbolSet<K> {
+        let mut set = self.clone();
+        set.difference_with(other);
+        set
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn set(ids: &[u32]) -> SymbolSet {
+        ids.iter().map(|&id| Symbol::new(id)).collect()
+    }
+
+    #[test]
+    fn test_symbol_set() {
+        let mut live = SymbolSet::with_capacity(10);
+        assert!(live.insert(Symbol::new(3)));
+        assert!(!live.insert(Symbol::new(3)));
+        assert!(live.insert(Symbol::new(130)));
+        assert!(live.contains(Symbol::new(130)));
+        assert!(!live.contains(Symbol::new(4)));
+        assert_eq!(live.len(), 2);
+        assert_eq!(
+            live.iter().collect::<Vec<_>>(),
+            [Symbol::new(3), Symbol::new(130)]
+        );
+        assert!(live.remove(Symbol::new(3)));
+        assert!(!live.remove(Symbol::new(500)));
+        assert_eq!(live, set(&[130]));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let (a, b) = (set(&[0, 63, 64, 200]), set(&[63, 100]));
+        assert_eq!(&a | &b, set(&[0, 63, 64, 100, 200]));
+        assert_eq!(&a & &b, set(&[63]));
+        assert_eq!(&a - &b, set(&[0, 64, 200]));
+
+        let mut c = b.clone();
+        assert!(c.union_with(&a));
+        assert!(!c.union_with(&a));
+        assert!(a.is_subset(&c) && !c.is_subset(&a));
+        assert!(c.intersect_with(&b));
+        assert_eq!(c, b);
+        assert!(!c.difference_with(&set(&[1])));
+        c.clear();
+        assert!(c.is_empty());
+    }
+}
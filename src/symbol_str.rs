@@ -0,0 +1,119 @@
+//! Serializes `Symbol`s as the strings they resolve to, re-interning them on
+//! deserialization. Intended to be used with serde's `with` attribute on
+//! `Symbol` fields of otherwise derived types:
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Ident {
+//!     #[serde(with = "lexicon::symbol_str")]
+//!     name: Symbol,
+//! }
+//! ```
+//!
+//! As derived implementations cannot be handed a `Lexicon`, the `Lexicon` to
+//! resolve with (or intern into) must be provided for the duration of the
+//! (de)serialization via `with_lexicon` (or `with_lexicon_mut`). Doing
+//! neither causes (de)serialization to fail with an error.
+
+use std::cell::Cell;
+
+use serde::de::{DeserializeSeed, Deserializer, Error as _};
+use serde::ser::{Error as _, Serializer};
+
+use crate::{Lexicon, Symbol, SymbolSeed};
+
+thread_local! {
+    static CONTEXT: Cell<Context> = const { Cell::new(Context::None) };
+}
+
+#[derive(Clone, Copy)]
+enum Context {
+    None,
+    Shared(*const Lexicon),
+    Exclusive(*mut Lexicon),
+}
+
+/// Restores the previous context when dropped, even if `f` panics.
+struct Restore(Context);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CONTEXT.with(|cx| cx.set(self.0));
+    }
+}
+
+fn scoped<R>(context: Context, f: impl FnOnce() -> R) -> R {
+    let _restore = Restore(CONTEXT.with(|cx| cx.replace(context)));
+    f()
+}
+
+/// Runs `f` with `lexicon` as the context for serializing `Symbol`s.
+pub fn with_lexicon<R>(lexicon: &Lexicon, f: impl FnOnce() -> R) -> R {
+    scoped(Context::Shared(lexicon), f)
+}
+
+/// Runs `f` with `lexicon` as the context for both serializing and
+/// deserializing `Symbol`s, the latter interning into `lexicon`.
+pub fn with_lexicon_mut<R>(lexicon: &mut Lexicon, f: impl FnOnce() -> R) -> R {
+    scoped(Context::Exclusive(lexicon), f)
+}
+
+pub fn serialize<S: Serializer>(symbol: &Symbol, serializer: S) -> Result<S::Ok, S::Error> {
+    // the pointers are only set while their borrow is held by `scoped`
+    let string = match CONTEXT.with(Cell::get) {
+        Context::None => return Err(S::Error::custom("no lexicon to resolve symbols with")),
+        Context::Shared(lexicon) => unsafe { &*lexicon }.lookup(*symbol),
+        Context::Exclusive(lexicon) => unsafe { &*lexicon }.lookup(*symbol),
+    };
+    serializer.serialize_str(string)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Symbol, D::Error> {
+    let lexicon = match CONTEXT.with(Cell::get) {
+        Context::Exclusive(lexicon) => lexicon,
+        _ => return Err(D::Error::custom("no lexicon to intern symbols into")),
+    };
+    // taken out of the context while deserializing, so that no other
+    // reference to the lexicon may be created in the meantime
+    scoped(Context::None, || {
+        SymbolSeed(unsafe { &mut *lexicon }).deserialize(deserializer)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_symbol_str_round_trip() {
+        let mut lexicon = Lexicon::default();
+        let names = [lexicon.intern("x"), lexicon.intern("y")];
+
+        let json = with_lexicon(&lexicon, || {
+            let strings = names
+                .iter()
+                .map(|sym| serialize(sym, serde_json::value::Serializer).unwrap())
+                .collect::<Vec<_>>();
+            serde_json::to_string(&strings).unwrap()
+        });
+        assert_eq!(json, r#"["x","y"]"#);
+
+        let mut fresh = Lexicon::default();
+        fresh.intern("y");
+        let values: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        let syms = with_lexicon_mut(&mut fresh, || {
+            values
+                .into_iter()
+                .map(|v| deserialize(v).unwrap())
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(fresh.lookup(syms[0]), "x");
+        assert_eq!(fresh.lookup(syms[1]), "y");
+    }
+
+    #[test]
+    fn test_missing_context() {
+        let sym = Lexicon::default().intern("nope");
+        assert!(serialize(&sym, serde_json::value::Serializer).is_err());
+    }
+}
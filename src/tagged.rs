@@ -0,0 +1,120 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+
+use crate::{Key, Symbol};
+
+/// `Symbol` tagged with a (typically zero-sized) marker type `T`, such that
+/// symbols of different namespaces are distinct types and can't be mixed up:
+///
+/// ```
+/// use lexicon::{Lexicon, TaggedSymbol};
+///
+/// enum Ident {}
+/// enum Label {}
+///
+/// let mut idents = Lexicon::<TaggedSymbol<Ident>>::with_key_capacity(64);
+/// let mut labels = Lexicon::<TaggedSymbol<Label>>::with_key_capacity(64);
+/// let x: TaggedSymbol<Ident> = idents.intern("x");
+/// let l: TaggedSymbol<Label> = labels.intern("'outer");
+/// assert_eq!(idents.lookup(x), "x");
+/// ```
+///
+/// The tag only exists at the type level: a `TaggedSymbol` has the same
+/// layout as a `Symbol`, and every trait is implemented regardless of `T`.
+pub struct TaggedSymbol<T> {
+    sym: Symbol,
+    tag: PhantomData<fn() -> T>,
+}
+
+impl<T> TaggedSymbol<T> {
+    /// Strips the tag off of this symbol.
+    pub fn untag(self) -> Symbol {
+        self.sym
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        self.sym.as_u32()
+    }
+
+    pub fn as_usize(&self) -> usize {
+        self.sym.as_usize()
+    }
+}
+
+impl<T> Clone for TaggedSymbol<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TaggedSymbol<T> {}
+
+impl<T> PartialEq for TaggedSymbol<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.sym == other.sym
+    }
+}
+
+impl<T> Eq for TaggedSymbol<T> {}
+
+impl<T> PartialOrd for TaggedSymbol<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for TaggedSymbol<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sym.cmp(&other.sym)
+    }
+}
+
+impl<T> Hash for TaggedSymbol<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.sym.hash(state)
+    }
+}
+
+impl<T> fmt::Debug for TaggedSymbol<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TaggedSymbol({})", self.as_u32())
+    }
+}
+
+impl<T> Key for TaggedSymbol<T> {
+    fn try_from_usize(idx: usize) -> Option<Self> {
+        Some(TaggedSymbol {
+            sym: Symbol::try_from_usize(idx)?,
+            tag: PhantomData,
+        })
+    }
+
+    fn to_usize(self) -> usize {
+        self.sym.as_usize()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Lexicon;
+
+    struct Ident;
+
+    #[test]
+    fn test_tagged() {
+        use core::mem::size_of;
+        assert_eq!(
+            size_of::<Option<TaggedSymbol<Ident>>>(),
+            size_of::<Symbol>()
+        );
+
+        let mut lexicon = Lexicon::<TaggedSymbol<Ident>>::with_key_capacity(16);
+        let sym = lexicon.intern("ident");
+        assert_eq!(lexicon.get("ident"), Some(sym));
+        assert_eq!(lexicon.lookup(sym), "ident");
+        assert_eq!(sym.untag().as_u32(), 0);
+    }
+}
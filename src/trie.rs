@@ -0,0 +1,185 @@
+use alloc::vec::Vec;
+
+/// Byte-wise trie mapping strings to their keys, backing the prefix index
+/// of a `Lexicon` (see `Lexicon::index_prefixes`).
+///
+/// Nodes are stored in a single `Vec` and linked through their first child
+/// and next sibling, so the trie takes no allocation per node. Siblings are
+/// kept sorted by their byte, such that walking the trie in pre-order
+/// visits strings in lexicographic order (UTF-8 preserving the order of
+/// `str`).
+#[derive(Clone, Debug)]
+pub(crate) struct Trie<K> {
+    /// Never empty: the first node is the root, matching the empty string.
+    nodes: Vec<Node<K>>,
+}
+
+#[derive(Clone, Debug)]
+struct Node<K> {
+    key: Option<K>,
+    byte: u8,
+    /// Index of the first child, or `NONE`.
+    child: u32,
+    /// Index of the next sibling (with a greater byte), or `NONE`.
+    next: u32,
+}
+
+/// Stands in for a missing child or sibling, as the root is neither.
+const NONE: u32 = 0;
+
+impl<K: Copy> Trie<K> {
+    pub fn new() -> Self {
+        Self {
+            nodes: alloc::vec![Node {
+                key: None,
+                byte: 0,
+                child: NONE,
+                next: NONE,
+            }],
+        }
+    }
+
+    pub fn insert(&mut self, string: &str, key: K) {
+        let mut node = 0;
+        for &byte in string.as_bytes() {
+            node = self.child_or_insert(node, byte);
+        }
+        self.nodes[node as usize].key = Some(key);
+    }
+
+    /// Returns the child of `node` for `byte`, inserting it in order among
+    /// its siblings if missing.
+    fn child_or_insert(&mut self, node: u32, byte: u8) -> u32 {
+        // the sibling the new node would follow, if not the first child
+        let mut prev = None;
+        let mut next = self.nodes[node as usize].child;
+        while next != NONE && self.nodes[next as usize].byte < byte {
+            prev = Some(next);
+            next = self.nodes[next as usize].next;
+        }
+        if next != NONE && self.nodes[next as usize].byte == byte {
+            return next;
+        }
+
+        let new = self.nodes.len() as u32;
+        self.nodes.push(Node {
+            key: None,
+            byte,
+            child: NONE,
+            next,
+        });
+        match prev {
+            Some(prev) => self.nodes[prev as usize].next = new,
+            None => self.nodes[node as usize].child = new,
+        }
+        new
+    }
+
+    fn child(&self, node: u32, byte: u8) -> Option<u32> {
+        let mut next = self.nodes[node as usize].child;
+        while next != NONE && self.nodes[next as usize].byte < byte {
+            next = self.nodes[next as usize].next;
+        }
+        (next != NONE && self.nodes[next as usize].byte == byte).then_some(next)
+    }
+
+    /// Returns the node matching `prefix`, if any string starts with it.
+    fn find(&self, prefix: &str) -> Option<u32> {
+        prefix
+            .bytes()
+            .try_fold(0, |node, byte| self.child(node, byte))
+    }
+
+    /// Returns the keys of every string starting with `prefix`, in
+    /// lexicographic order.
+    pub fn with_prefix(&self, prefix: &str) -> impl Iterator<Item = K> + '_ {
+        // pairs of a node along with whether its siblings are to be visited
+        let mut stack = Vec::new();
+        stack.extend(self.find(prefix).map(|node| (node, false)));
+        core::iter::from_fn(move || loop {
+            let (node, siblings) = stack.pop()?;
+            let node = &self.nodes[node as usize];
+            if siblings && node.next != NONE {
+                stack.push((node.next, true));
+            }
+            if node.child != NONE {
+                stack.push((node.child, true));
+            }
+            if node.key.is_some() {
+                return node.key;
+            }
+        })
+    }
+
+    /// Returns the key of the longest string which `input` starts with,
+    /// along with its length.
+    pub fn longest_prefix_of(&self, input: &str) -> Option<(K, usize)> {
+        let mut node = 0;
+        let mut longest = self.nodes[0].key.map(|key| (key, 0));
+        for (len, byte) in input.bytes().enumerate() {
+            node = match self.child(node, byte) {
+                Some(child) => child,
+                None => break,
+            };
+            if let Some(key) = self.nodes[node as usize].key {
+                longest = Some((key, len + 1));
+            }
+        }
+        longest
+    }
+
+    /// Removes every key for which `f` returns `false`. Nodes are kept, as
+    /// they are likely to be reused.
+    pub fn retain(&mut self, mut f: impl FnMut(K) -> bool) {
+        for node in &mut self.nodes {
+            node.key = node.key.filter(|&key| f(key));
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.truncate(1);
+        self.nodes[0] = Node {
+            key: None,
+            byte: 0,
+            child: NONE,
+            next: NONE,
+        };
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.nodes.shrink_to_fit();
+    }
+
+    pub fn allocation_size(&self) -> usize {
+        self.nodes.capacity() * core::mem::size_of::<Node<K>>()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_trie_order() {
+        let mut trie = Trie::new();
+        for (key, string) in ["b", "ab", "", "a", "\u{e9}", "abc", "aa"]
+            .iter()
+            .enumerate()
+        {
+            trie.insert(string, key);
+        }
+        assert_eq!(
+            trie.with_prefix("").collect::<Vec<_>>(),
+            [2, 3, 6, 1, 5, 0, 4]
+        );
+        assert_eq!(trie.with_prefix("ab").collect::<Vec<_>>(), [1, 5]);
+        assert_eq!(trie.with_prefix("abcd").count(), 0);
+
+        assert_eq!(trie.longest_prefix_of("abcd"), Some((5, 3)));
+        assert_eq!(trie.longest_prefix_of("ac"), Some((3, 1)));
+        assert_eq!(trie.longest_prefix_of("c"), Some((2, 0)));
+
+        trie.retain(|key| key != 5);
+        assert_eq!(trie.with_prefix("ab").collect::<Vec<_>>(), [1]);
+    }
+}
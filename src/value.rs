@@ -0,0 +1,161 @@
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+
+use hashbrown::{DefaultHashBuilder, HashTable};
+
+use crate::{Key, LexiconError, Symbol};
+
+/// Interner for arbitrary owned values (e.g., type representations or
+/// constant payloads), deduplicating them behind copyable keys the same way
+/// a `Lexicon` does for strings.
+///
+/// Values are stored once, in the order they were first interned, and
+/// resolved back through `lookup`.
+#[derive(Clone, Debug)]
+pub struct ValueInterner<T, K = Symbol, H = DefaultHashBuilder> {
+    map: HashTable<K>,
+    hasher: H,
+    values: Vec<T>,
+}
+
+impl<T: Hash + Eq> ValueInterner<T> {
+    pub fn new() -> Self {
+        Self::with_hasher(DefaultHashBuilder::default())
+    }
+}
+
+impl<T: Hash + Eq, K: Key, H: BuildHasher> ValueInterner<T, K, H> {
+    pub fn with_hasher(hasher: H) -> Self {
+        Self {
+            map: HashTable::new(),
+            hasher,
+            values: Vec::new(),
+        }
+    }
+
+    pub fn intern(&mut self, value: T) -> K {
+        match self.try_intern(value) {
+            Ok(id) => id,
+            Err(e) => panic!("{} while interning a value", e),
+        }
+    }
+
+    /// Like `intern`, but returns `LexiconError::KeysExhausted` rather than
+    /// panicking if `value` is new and no key is left for it.
+    pub fn try_intern(&mut self, value: T) -> Result<K, LexiconError> {
+        let hash = self.hasher.hash_one(&value);
+        if let Some(id) = self.find(hash, &value) {
+            return Ok(id);
+        }
+
+        let id = K::try_from_usize(self.values.len()).ok_or(LexiconError::KeysExhausted)?;
+        self.values.push(value);
+        let (values, hasher) = (&self.values, &self.hasher);
+        self.map
+            .insert_unique(hash, id, |id| hasher.hash_one(&values[id.to_usize()]));
+        Ok(id)
+    }
+
+    /// Returns the key of the value equal to `value` if it has already been
+    /// interned, where `value` may be any borrowed form of `T` (such as a
+    /// slice for a `Vec`).
+    pub fn get<Q>(&self, value: &Q) -> Option<K>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(self.hasher.hash_one(value), value)
+    }
+
+    fn find<Q>(&self, hash: u64, value: &Q) -> Option<K>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let values = &self.values;
+        self.map
+            .find(hash, |id| values[id.to_usize()].borrow() == value)
+            .copied()
+    }
+
+    pub fn lookup(&self, id: K) -> &T {
+        match self.try_lookup(id) {
+            Some(value) => value,
+            None => panic!("symbol `{}` was not interned", id.to_usize()),
+        }
+    }
+
+    pub fn try_lookup(&self, id: K) -> Option<&T> {
+        self.values.get(id.to_usize())
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns an iterator over every key along with its value, in the order
+    /// they were interned.
+    pub fn iter(&self) -> impl Iterator<Item = (K, &T)> + '_ {
+        self.values.iter().enumerate().map(|(idx, value)| {
+            (
+                K::try_from_usize(idx).expect("interned value has no key"),
+                value,
+            )
+        })
+    }
+
+    /// Consumes this interner, returning its values indexed by key.
+    pub fn into_values(self) -> Vec<T> {
+        self.values
+    }
+}
+
+impl<T: Hash + Eq, K: Key, H: BuildHasher + Default> Default for ValueInterner<T, K, H> {
+    fn default() -> Self {
+        Self::with_hasher(H::default())
+    }
+}
+
+impl<T: Hash + Eq, K: Key, H: BuildHasher> core::ops::Index<K> for ValueInterner<T, K, H> {
+    type Output = T;
+
+    fn index(&self, index: K) -> &Self::Output {
+        self.lookup(index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    #[derive(Debug, Hash, PartialEq, Eq)]
+    enum Type {
+        Int,
+        Tuple(Vec<Type>),
+    }
+
+    #[test]
+    fn test_values() {
+        let mut types = ValueInterner::new();
+        let pair = types.intern(Type::Tuple(vec![Type::Int, Type::Int]));
+        let int = types.intern(Type::Int);
+        assert_eq!(types.intern(Type::Tuple(vec![Type::Int, Type::Int])), pair);
+        assert_eq!(types[int], Type::Int);
+        assert_eq!(types.get(&Type::Tuple(vec![])), None);
+        assert_eq!(types.len(), 2);
+    }
+
+    #[test]
+    fn test_borrowed_get() {
+        let mut consts = ValueInterner::<Vec<u8>, u16>::with_hasher(DefaultHashBuilder::default());
+        let id = consts.intern(vec![1, 2, 3]);
+        assert_eq!(consts.get(&[1, 2, 3][..]), Some(id));
+        assert_eq!(consts.iter().collect::<Vec<_>>(), [(0, &vec![1, 2, 3])]);
+    }
+}
@@ -0,0 +1,92 @@
+use core::fmt;
+use core::hash::BuildHasher;
+
+use crate::{Backend, Key, Lexicon};
+
+/// Pending string being built up piece by piece (through `fmt::Write`), to
+/// be interned once complete. Created by `Lexicon::start_symbol`.
+///
+/// Pieces are written into a buffer the `Lexicon` reuses across writers, so
+/// building a string this way does not allocate (past the buffer's growth).
+/// Dropping the writer without calling `finish` discards the string.
+pub struct SymbolWriter<'a, K: Key, H: BuildHasher, B: Backend> {
+    lexicon: &'a mut Lexicon<K, H, B>,
+}
+
+impl<K: Key, H: BuildHasher, B: Backend> Lexicon<K, H, B> {
+    /// Starts building a string to be interned, e.g., a mangled name:
+    ///
+    /// ```
+    /// use std::fmt::Write;
+    /// # use lexicon::Lexicon;
+    ///
+    /// let mut lexicon = Lexicon::default();
+    /// let mut writer = lexicon.start_symbol();
+    /// for segment in ["std", "vec", "Vec"] {
+    ///     write!(writer, "{}{}", segment.len(), segment).unwrap();
+    /// }
+    /// let sym = writer.finish();
+    /// assert_eq!(lexicon.lookup(sym), "3std3vec3Vec");
+    /// ```
+    pub fn start_symbol(&mut self) -> SymbolWriter<'_, K, H, B> {
+        self.scratch.clear();
+        SymbolWriter { lexicon: self }
+    }
+}
+
+impl<K: Key, H: BuildHasher, B: Backend> SymbolWriter<'_, K, H, B> {
+    /// Returns the string written so far.
+    pub fn as_str(&self) -> &str {
+        &self.lexicon.scratch
+    }
+
+    /// Interns the string written so far.
+    pub fn finish(self) -> K {
+        let buf = core::mem::take(&mut self.lexicon.scratch);
+        let id = self.lexicon.intern(&buf);
+        self.lexicon.scratch = buf;
+        id
+    }
+}
+
+impl<K: Key, H: BuildHasher, B: Backend> fmt::Write for SymbolWriter<'_, K, H, B> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.lexicon.scratch.push_str(s);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.lexicon.scratch.push(c);
+        Ok(())
+    }
+}
+
+impl<K: Key, H: BuildHasher, B: Backend> fmt::Debug for SymbolWriter<'_, K, H, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SymbolWriter").field(&self.as_str()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::fmt::Write;
+
+    #[test]
+    fn test_symbol_writer() {
+        let mut lexicon = Lexicon::default();
+        let existing = lexicon.intern("a::b");
+
+        let mut writer = lexicon.start_symbol();
+        writer.write_str("a").unwrap();
+        let b = 'b';
+        write!(writer, "::{}", b).unwrap();
+        assert_eq!(writer.as_str(), "a::b");
+        assert_eq!(writer.finish(), existing);
+
+        lexicon.start_symbol().write_str("discarded").unwrap();
+        let sym = lexicon.start_symbol().finish();
+        assert_eq!(lexicon.lookup(sym), "");
+        assert_eq!(lexicon.get("discarded"), None);
+    }
+}